@@ -0,0 +1,167 @@
+use embedded_hal::blocking::i2c;
+use embedded_hal::PwmPin;
+
+use crate::device::{Tps6507x, Tps6507xResult};
+
+/// White LED backlight handle, driving the brightness as a PWM pin
+///
+/// `PwmPin` methods can not report errors, so interface errors are ignored by them. Duty is the
+/// brightness in percent, the last value read or written is cached by the handle.
+pub struct Backlight<'a, I2C> {
+    tps: &'a mut Tps6507x<I2C>,
+    duty: u8,
+}
+
+impl<'a, I2C, E> Backlight<'a, I2C>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    pub(crate) fn new(tps: &'a mut Tps6507x<I2C>) -> Tps6507xResult<Self, E> {
+        let duty = tps.backlight_brightness()?;
+        Ok(Self { tps, duty })
+    }
+}
+
+impl<'a, I2C, E> PwmPin for Backlight<'a, I2C>
+where
+    I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+{
+    type Duty = u8;
+
+    fn disable(&mut self) {
+        let _ = self.tps.set_backlight_enable(false);
+    }
+
+    fn enable(&mut self) {
+        let _ = self.tps.set_backlight_enable(true);
+    }
+
+    fn get_duty(&self) -> Self::Duty {
+        self.duty
+    }
+
+    fn get_max_duty(&self) -> Self::Duty {
+        100
+    }
+
+    fn set_duty(&mut self, duty: Self::Duty) {
+        let duty = duty.min(100);
+        if self.tps.set_backlight_brightness(duty).is_ok() {
+            self.duty = duty;
+        }
+    }
+}
+
+/// Gamma 2.2 correction table, perceived brightness in percent to duty cycle in percent
+const GAMMA: [u8; 101] = [
+    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, //
+    1, 1, 1, 1, 1, 2, 2, 2, 2, 3, //
+    3, 3, 4, 4, 4, 5, 5, 6, 6, 7, //
+    7, 8, 8, 9, 9, 10, 11, 11, 12, 13, //
+    13, 14, 15, 16, 16, 17, 18, 19, 20, 21, //
+    22, 23, 24, 25, 26, 27, 28, 29, 30, 31, //
+    33, 34, 35, 36, 37, 39, 40, 41, 43, 44, //
+    46, 47, 49, 50, 52, 53, 55, 56, 58, 60, //
+    61, 63, 65, 66, 68, 70, 72, 74, 75, 77, //
+    79, 81, 83, 85, 87, 89, 91, 94, 96, 98, //
+    100,
+];
+
+/// Converts perceived brightness in percent, clamped to 100, into duty cycle in percent
+pub fn gamma_correct(level: u8) -> u8 {
+    GAMMA[level.min(100) as usize]
+}
+
+/// Brightness change curve over the fade duration
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Easing {
+    Linear,
+    /// Starts slow, accelerates
+    EaseIn,
+    /// Starts fast, decelerates
+    EaseOut,
+    /// Starts and ends slow
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps fade progress in permille into eased progress in permille
+    fn apply(self, t: u32) -> u32 {
+        use Easing::*;
+        match self {
+            Linear => t,
+            EaseIn => t * t / 1000,
+            EaseOut => 1000 - (1000 - t) * (1000 - t) / 1000,
+            EaseInOut if t < 500 => 2 * t * t / 1000,
+            EaseInOut => 1000 - 2 * (1000 - t) * (1000 - t) / 1000,
+        }
+    }
+}
+
+/// Non-blocking backlight brightness ramp
+///
+/// Brightness levels are perceived brightness in percent, gamma corrected before written to the
+/// device. Time is a free running millisecond counter supplied by the caller, wrap around is
+/// handled.
+#[derive(Debug)]
+pub struct BacklightFade {
+    from: u8,
+    to: u8,
+    start_ms: u32,
+    duration_ms: u32,
+    easing: Easing,
+    duty: Option<u8>,
+    finished: bool,
+}
+
+impl BacklightFade {
+    /// Starts a fade from `from` to `to` brightness at `now_ms`
+    pub fn new(from: u8, to: u8, duration_ms: u32, easing: Easing, now_ms: u32) -> Self {
+        Self {
+            from: from.min(100),
+            to: to.min(100),
+            start_ms: now_ms,
+            duration_ms,
+            easing,
+            duty: None,
+            finished: false,
+        }
+    }
+
+    /// Perceived brightness in percent at `now_ms`
+    pub fn level(&self, now_ms: u32) -> u8 {
+        let elapsed = now_ms.wrapping_sub(self.start_ms);
+        if elapsed >= self.duration_ms {
+            return self.to;
+        }
+        let t = (elapsed as u64 * 1000 / self.duration_ms as u64) as u32;
+        let progress = self.easing.apply(t) as i32;
+        let delta = self.to as i32 - self.from as i32;
+        (self.from as i32 + delta * progress / 1000) as u8
+    }
+
+    /// Returns `true` once the target brightness has been written
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the fade, writes brightness only if the duty cycle step changed
+    ///
+    /// Returns `true` once the fade is finished.
+    pub fn tick<I2C, E>(&mut self, tps: &mut Tps6507x<I2C>, now_ms: u32) -> Tps6507xResult<bool, E>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    {
+        if self.finished {
+            return Ok(true);
+        }
+        let level = self.level(now_ms);
+        let duty = gamma_correct(level);
+        if self.duty != Some(duty) {
+            tps.set_backlight_brightness(duty)?;
+            self.duty = Some(duty);
+        }
+        self.finished = level == self.to && now_ms.wrapping_sub(self.start_ms) >= self.duration_ms;
+        Ok(self.finished)
+    }
+}
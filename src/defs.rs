@@ -199,3 +199,15 @@ impl From<DCDCVoltage> for u8 {
         v as u8
     }
 }
+
+impl DCDCVoltage {
+    /// Output voltage in millivolts
+    pub fn millivolts(self) -> u16 {
+        let code = self as u16;
+        match code {
+            0..=31 => 725 + 25 * code,
+            32..=59 => 1550 + 50 * (code - 32),
+            _ => 3000 + 100 * (code - 60),
+        }
+    }
+}
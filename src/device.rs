@@ -1,12 +1,25 @@
+use embedded_hal::adc::Channel;
+use embedded_hal::blocking::delay::{DelayMs, DelayUs};
 use embedded_hal::blocking::i2c::{Write, WriteRead};
 
+use crate::backlight::Backlight;
 use crate::defs::*;
+use crate::ntc::Thermistor;
+use crate::oneshot::channel::VrefParam;
 use crate::regs::*;
 
 #[derive(Debug)]
 pub enum Tps6507xError<E> {
     /// Performed read back mismatched with previously wrote value
-    ReadBackMismatch,
+    ReadBackMismatch {
+        register: Registers,
+        expected: u8,
+        actual: u8,
+    },
+    /// Operation did not complete in time
+    Timeout,
+    /// Another ADC conversion is pending
+    AdcBusy,
     /// Propagated error from the interface
     Interface(E),
 }
@@ -15,18 +28,29 @@ pub type Tps6507xResult<T, E> = Result<T, Tps6507xError<E>>;
 
 pub struct Tps6507x<I2C> {
     i2c: I2C,
+    verify_writes: bool,
+    interrupt_mask: int::InterruptMask,
+    adc_pending: Option<adc::AdcInputSelect>,
 }
 
 /// Tps6507x has single i2c slave address
 pub const SLAVE_ADDR: u8 = 0x48;
 
+/// Interval between END OF CONVERSION polls of blocking ADC reads
+pub const ADC_POLL_INTERVAL_US: u16 = 50;
+
 impl<I2C, E> Tps6507x<I2C>
 where
     I2C: WriteRead<Error = E> + Write<Error = E>,
 {
     /// Create driver instance
     pub fn new(i2c: I2C) -> Self {
-        Self { i2c }
+        Self {
+            i2c,
+            verify_writes: false,
+            interrupt_mask: int::InterruptMask::default(),
+            adc_pending: None,
+        }
     }
 
     /// Destroy driver and free interface
@@ -34,13 +58,32 @@ where
         self.i2c
     }
 
+    /// Enables read back verification of every register write performed by the driver
+    ///
+    /// Only bits which are writable and not changed by the device itself are compared, on
+    /// mismatch `Tps6507xError::ReadBackMismatch` is returned.
+    pub fn set_verify_writes(&mut self, enable: bool) {
+        self.verify_writes = enable;
+    }
+
+    /// Get read back verification flag
+    pub fn verify_writes(&self) -> bool {
+        self.verify_writes
+    }
+
     /// Config power path control register
     pub fn set_power_path(&mut self, power_path: ppath::PowerPath) -> Tps6507xResult<(), E> {
         let reg: ppath::PPATH1 = power_path.into();
-        self.write_register_raw(Registers::PPATH1, reg.0)?;
+        self.write_register(Registers::PPATH1, reg.0)?;
         Ok(())
     }
 
+    /// Get power path configuration
+    pub fn power_path(&mut self) -> Tps6507xResult<ppath::PowerPath, E> {
+        let reg = self.read_register_raw(Registers::PPATH1)?;
+        Ok(ppath::PPATH1(reg).into())
+    }
+
     /// Get USB power enable flag
     pub fn usb_power(&mut self) -> Tps6507xResult<bool, E> {
         let reg = self.read_register_raw(Registers::PPATH1)?;
@@ -53,73 +96,797 @@ where
         Ok(ppath::PPATH1(reg).ac_power())
     }
 
+    /// Selects which interrupt sources are allowed to pull the INT pin low
+    pub fn set_interrupt_mask(&mut self, mask: int::InterruptMask) -> Tps6507xResult<(), E> {
+        let reg: int::INT = mask.into();
+        self.write_register(Registers::INT, reg.0)?;
+        self.interrupt_mask = mask;
+        Ok(())
+    }
+
+    /// Get interrupt mask last written by the driver, all sources masked after UVLO
+    ///
+    /// The mask is not read from the device, as reading INT clears latched interrupt events.
+    pub fn interrupt_mask(&self) -> int::InterruptMask {
+        self.interrupt_mask
+    }
+
+    /// Reads and clears latched interrupt events, intended to be called from INT pin handler
+    pub fn service_interrupt(&mut self) -> Tps6507xResult<int::InterruptEvents, E> {
+        let reg = self.read_register_raw(Registers::INT)?;
+        Ok(int::INT(reg).into())
+    }
+
+    /// Get battery charger status
+    pub fn charger_status(&mut self) -> Tps6507xResult<chgconfig::ChargerStatus, E> {
+        let ppath1 = ppath::PPATH1(self.read_register_raw(Registers::PPATH1)?);
+        let chgconfig0 = chgconfig::CHGCONFIG0(self.read_register_raw(Registers::CHGCONFIG0)?);
+        let chgconfig3 = chgconfig::CHGCONFIG3(self.read_register_raw(Registers::CHGCONFIG3)?);
+        Ok(chgconfig::ChargerStatus::from_regs(
+            ppath1, chgconfig0, chgconfig3,
+        ))
+    }
+
     /// Set charger configuration
     pub fn set_charger_config(
         &mut self,
         config: chgconfig::ChargerConfig,
     ) -> Tps6507xResult<(), E> {
         let reg: chgconfig::CHGCONFIG1 = config.into();
-        self.write_register_raw(Registers::CHGCONFIG1, reg.0)?;
+        self.write_register(Registers::CHGCONFIG1, reg.0)?;
+        Ok(())
+    }
+
+    /// Get charger configuration
+    pub fn charger_config(&mut self) -> Tps6507xResult<chgconfig::ChargerConfig, E> {
+        let reg = self.read_register_raw(Registers::CHGCONFIG1)?;
+        Ok(chgconfig::CHGCONFIG1(reg).into())
+    }
+
+    /// Set charge voltage, precharge voltage and dynamic timer configuration
+    pub fn set_charger_config2(
+        &mut self,
+        config: chgconfig::ChargerConfig2,
+    ) -> Tps6507xResult<(), E> {
+        let reg: chgconfig::CHGCONFIG2 = config.into();
+        self.write_register(Registers::CHGCONFIG2, reg.0)?;
+        Ok(())
+    }
+
+    /// Get charge voltage, precharge voltage and dynamic timer configuration
+    pub fn charger_config2(&mut self) -> Tps6507xResult<chgconfig::ChargerConfig2, E> {
+        let reg = self.read_register_raw(Registers::CHGCONFIG2)?;
+        Ok(chgconfig::CHGCONFIG2(reg).into())
+    }
+
+    /// Set DPPM threshold, precharge time, termination current and input current sinks
+    pub fn set_charger_config3(
+        &mut self,
+        config: chgconfig::ChargerConfig3,
+    ) -> Tps6507xResult<(), E> {
+        let reg: chgconfig::CHGCONFIG3 = config.into();
+        self.write_register(Registers::CHGCONFIG3, reg.0)?;
         Ok(())
     }
 
+    /// Get DPPM threshold, precharge time, termination current and input current sinks
+    pub fn charger_config3(&mut self) -> Tps6507xResult<chgconfig::ChargerConfig3, E> {
+        let reg = self.read_register_raw(Registers::CHGCONFIG3)?;
+        Ok(chgconfig::CHGCONFIG3(reg).into())
+    }
+
     /// Assert/Deassert charger reset bit
     pub fn set_charger_reset(&mut self, assert: bool) -> Tps6507xResult<(), E> {
         let mut reg = chgconfig::CHGCONFIG1(self.read_register_raw(Registers::CHGCONFIG1)?);
         reg.set_charger_reset(assert);
-        self.write_register_raw(Registers::CHGCONFIG1, reg.0)?;
+        self.write_register(Registers::CHGCONFIG1, reg.0)?;
         Ok(())
     }
 
+    /// Get charger reset bit
+    pub fn charger_reset(&mut self) -> Tps6507xResult<bool, E> {
+        let reg = self.read_register_raw(Registers::CHGCONFIG1)?;
+        Ok(chgconfig::CHGCONFIG1(reg).charger_reset())
+    }
+
+    /// Enables the rail, the rail is on only if its enable pin is HIGH as well
+    pub fn enable_rail(&mut self, rail: ctrl::Rail) -> Tps6507xResult<(), E> {
+        self.set_rail_enable(rail, true)
+    }
+
+    /// Disables the rail
+    pub fn disable_rail(&mut self, rail: ctrl::Rail) -> Tps6507xResult<(), E> {
+        self.set_rail_enable(rail, false)
+    }
+
+    fn set_rail_enable(&mut self, rail: ctrl::Rail, enable: bool) -> Tps6507xResult<(), E> {
+        let mut reg = ctrl::CON_CTRL1(self.read_register_raw(Registers::CON_CTRL1)?);
+        let mut rails = ctrl::RailSet::from_bits(reg.rails());
+        rails.set(rail, enable);
+        reg.set_rails(rails.bits());
+        self.write_register(Registers::CON_CTRL1, reg.0)?;
+        Ok(())
+    }
+
+    /// Get rail enable bit
+    ///
+    /// Note: the device resets the bit to default while the rail enable pin is LOW.
+    pub fn rail_enabled(&mut self, rail: ctrl::Rail) -> Tps6507xResult<bool, E> {
+        Ok(self.rails()?.contains(rail))
+    }
+
+    /// Enables rails in the set and disables all others with a single register write
+    pub fn set_rails(&mut self, rails: ctrl::RailSet) -> Tps6507xResult<(), E> {
+        let mut reg = ctrl::CON_CTRL1(self.read_register_raw(Registers::CON_CTRL1)?);
+        reg.set_rails(rails.bits());
+        self.write_register(Registers::CON_CTRL1, reg.0)?;
+        Ok(())
+    }
+
+    /// Get set of enabled rails
+    pub fn rails(&mut self) -> Tps6507xResult<ctrl::RailSet, E> {
+        let reg = ctrl::CON_CTRL1(self.read_register_raw(Registers::CON_CTRL1)?);
+        Ok(ctrl::RailSet::from_bits(reg.rails()))
+    }
+
+    /// Sets power-up sequencing of DCDC converters and LDOs
+    pub fn set_sequence(&mut self, sequence: ctrl::SequenceMode) -> Tps6507xResult<(), E> {
+        let mut reg = ctrl::CON_CTRL1(self.read_register_raw(Registers::CON_CTRL1)?);
+        reg.set_dcdc_seq(sequence.dcdc);
+        self.write_register(Registers::CON_CTRL1, reg.0)?;
+
+        let mut reg = ldo::LDO_CTRL1(self.read_register_raw(Registers::LDO_CTRL1)?);
+        reg.set_ldo_seq(sequence.ldo);
+        self.write_register(Registers::LDO_CTRL1, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets power-up sequencing of DCDC converters and LDOs
+    pub fn sequence(&mut self) -> Tps6507xResult<ctrl::SequenceMode, E> {
+        let con_ctrl1 = ctrl::CON_CTRL1(self.read_register_raw(Registers::CON_CTRL1)?);
+        let ldo_ctrl1 = ldo::LDO_CTRL1(self.read_register_raw(Registers::LDO_CTRL1)?);
+        Ok(ctrl::SequenceMode {
+            dcdc: con_ctrl1.dcdc_seq(),
+            ldo: ldo_ctrl1.ldo_seq(),
+        })
+    }
+
+    /// Sets state machine timers, UVLO and deep sleep control configuration
+    pub fn set_control_config(&mut self, config: ctrl::ControlConfig) -> Tps6507xResult<(), E> {
+        let reg: ctrl::CON_CTRL2 = config.into();
+        self.write_register(Registers::CON_CTRL2, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets state machine timers, UVLO and deep sleep control configuration
+    pub fn control_config(&mut self) -> Tps6507xResult<ctrl::ControlConfig, E> {
+        let reg = self.read_register_raw(Registers::CON_CTRL2)?;
+        Ok(ctrl::CON_CTRL2(reg).into())
+    }
+
+    /// Sets undervoltage lockout threshold and hysteresis
+    pub fn set_uvlo(
+        &mut self,
+        threshold: ctrl::UvloThreshold,
+        hysteresis: ctrl::UvloHysteresis,
+    ) -> Tps6507xResult<(), E> {
+        let mut reg = ctrl::CON_CTRL2(self.read_register_raw(Registers::CON_CTRL2)?);
+        reg.set_uvlo(threshold);
+        reg.set_uvlo_hysteresis(hysteresis);
+        self.write_register(Registers::CON_CTRL2, reg.0)?;
+        Ok(())
+    }
+
+    /// Sets delay time of the reset and PGOOD outputs
+    pub fn set_power_good_delay(&mut self, delay: pgood::PowerGoodDelay) -> Tps6507xResult<(), E> {
+        let mut reg = pgood::PGOOD(self.read_register_raw(Registers::PGOOD)?);
+        reg.set_delay(delay);
+        self.write_register(Registers::PGOOD, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets delay time of the reset and PGOOD outputs
+    pub fn power_good_delay(&mut self) -> Tps6507xResult<pgood::PowerGoodDelay, E> {
+        let reg = self.read_register_raw(Registers::PGOOD)?;
+        Ok(pgood::PGOOD(reg).delay())
+    }
+
+    /// Forces the converter into fixed frequency PWM mode instead of PWM/PFM power save mode
+    pub fn set_forced_pwm(&mut self, converter: dcdc::Dcdc, enable: bool) -> Tps6507xResult<(), E> {
+        let mut reg = ctrl::CON_CTRL3(self.read_register_raw(Registers::CON_CTRL3)?);
+        match converter {
+            dcdc::Dcdc::Dcdc1 => reg.set_fpwm_dcdc1(enable),
+            dcdc::Dcdc::Dcdc2 => reg.set_fpwm_dcdc2(enable),
+            dcdc::Dcdc::Dcdc3 => reg.set_fpwm_dcdc3(enable),
+        }
+        self.write_register(Registers::CON_CTRL3, reg.0)?;
+        Ok(())
+    }
+
+    /// Get forced PWM mode flag of the converter
+    pub fn forced_pwm(&mut self, converter: dcdc::Dcdc) -> Tps6507xResult<bool, E> {
+        let reg = ctrl::CON_CTRL3(self.read_register_raw(Registers::CON_CTRL3)?);
+        Ok(match converter {
+            dcdc::Dcdc::Dcdc1 => reg.fpwm_dcdc1(),
+            dcdc::Dcdc::Dcdc2 => reg.fpwm_dcdc2(),
+            dcdc::Dcdc::Dcdc3 => reg.fpwm_dcdc3(),
+        })
+    }
+
+    /// Enables active discharge of the rail output capacitor when the rail is disabled
+    pub fn set_discharge(&mut self, rail: ctrl::Rail, enable: bool) -> Tps6507xResult<(), E> {
+        let mut reg = ctrl::CON_CTRL3(self.read_register_raw(Registers::CON_CTRL3)?);
+        let mut rails = ctrl::RailSet::from_bits(reg.discharge());
+        rails.set(rail, enable);
+        reg.set_discharge(rails.bits());
+        self.write_register(Registers::CON_CTRL3, reg.0)?;
+        Ok(())
+    }
+
+    /// Get set of rails with enabled output discharge
+    pub fn discharge(&mut self) -> Tps6507xResult<ctrl::RailSet, E> {
+        let reg = ctrl::CON_CTRL3(self.read_register_raw(Registers::CON_CTRL3)?);
+        Ok(ctrl::RailSet::from_bits(reg.discharge()))
+    }
+
+    /// Get power good status of the rails and the reset comparator
+    pub fn power_good(&mut self) -> Tps6507xResult<pgood::PowerGood, E> {
+        let reg = self.read_register_raw(Registers::PGOOD)?;
+        Ok(pgood::PGOOD(reg).into())
+    }
+
+    /// Polls power good status every millisecond until all `rails` are good
+    pub fn wait_power_good<D: DelayMs<u8>>(
+        &mut self,
+        rails: ctrl::RailSet,
+        delay: &mut D,
+        timeout_ms: u32,
+    ) -> Tps6507xResult<pgood::PowerGood, E> {
+        let mut elapsed_ms = 0;
+        loop {
+            let status = self.power_good()?;
+            if status.rails.bits() & rails.bits() == rails.bits() {
+                return Ok(status);
+            }
+            if elapsed_ms >= timeout_ms {
+                return Err(Tps6507xError::Timeout);
+            }
+            delay.delay_ms(1);
+            elapsed_ms += 1;
+        }
+    }
+
+    /// Selects which rails drive the PGOOD output
+    pub fn set_power_good_mask(&mut self, mask: pgood::PowerGoodMask) -> Tps6507xResult<(), E> {
+        let reg: pgood::PGOODMASK = mask.into();
+        self.write_register(Registers::PGOODMASK, reg.0)?;
+        Ok(())
+    }
+
+    /// Get rails which drive the PGOOD output
+    pub fn power_good_mask(&mut self) -> Tps6507xResult<pgood::PowerGoodMask, E> {
+        let reg = self.read_register_raw(Registers::PGOODMASK)?;
+        Ok(pgood::PGOODMASK(reg).into())
+    }
+
     /// Sets the output voltage for the DCDC1 converter
     pub fn set_dcdc1(&mut self, voltage: DCDCVoltage) -> Tps6507xResult<(), E> {
-        self.write_register_raw(Registers::DEFDCDC1, voltage as u8)?;
+        let mut reg = dcdc::DEFDCDC1(self.read_register_raw(Registers::DEFDCDC1)?);
+        reg.set_dcdc1(voltage);
+        self.write_register(Registers::DEFDCDC1, reg.0)?;
         Ok(())
     }
 
+    /// Gets the output voltage for the DCDC1 converter
+    pub fn dcdc1(&mut self) -> Tps6507xResult<DCDCVoltage, E> {
+        let reg = self.read_register_raw(Registers::DEFDCDC1)?;
+        Ok(dcdc::DEFDCDC1(reg).dcdc1())
+    }
+
+    /// Sets the output voltage for DCDC2 selected when the DEFDCDC2 pin is LOW
+    pub fn set_dcdc2_low(&mut self, voltage: DCDCVoltage) -> Tps6507xResult<(), E> {
+        let mut reg = dcdc::DEFDCDC2_LOW(0x00);
+        reg.set_dcdc2(voltage);
+        self.write_register(Registers::DEFDCDC2_LOW, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets the output voltage for DCDC2 selected when the DEFDCDC2 pin is LOW
+    pub fn dcdc2_low(&mut self) -> Tps6507xResult<DCDCVoltage, E> {
+        let reg = self.read_register_raw(Registers::DEFDCDC2_LOW)?;
+        Ok(dcdc::DEFDCDC2_LOW(reg).dcdc2())
+    }
+
     /// The output voltage for DCDC2 is switched between the value defined in DEFDCDC2_LOW and
     /// DEFDCDC2_HIGH depending on the status of the DEFDCDC2 pin. If DEFDCDC2 is LOW the value in
     /// DEFDCDC2_LOW is selected, if DEFDCDC2 = HIGH, the value in DEFDCDC2_HIGH is selected.
     pub fn set_dcdc2_high(&mut self, voltage: DCDCVoltage) -> Tps6507xResult<(), E> {
-        self.write_register_raw(Registers::DEFDCDC2_HIGH, voltage as u8)?;
+        let mut reg = dcdc::DEFDCDC2_HIGH(self.read_register_raw(Registers::DEFDCDC2_HIGH)?);
+        reg.set_dcdc2(voltage);
+        self.write_register(Registers::DEFDCDC2_HIGH, reg.0)?;
         Ok(())
     }
 
+    /// Gets the output voltage for DCDC2 selected when the DEFDCDC2 pin is HIGH
+    pub fn dcdc2_high(&mut self) -> Tps6507xResult<DCDCVoltage, E> {
+        let reg = self.read_register_raw(Registers::DEFDCDC2_HIGH)?;
+        Ok(dcdc::DEFDCDC2_HIGH(reg).dcdc2())
+    }
+
+    /// Gets the DCDC2 output voltage currently selected by the DEFDCDC2 pin state
+    pub fn dcdc2_active(&mut self, defdcdc2_high: bool) -> Tps6507xResult<DCDCVoltage, E> {
+        if defdcdc2_high {
+            self.dcdc2_high()
+        } else {
+            self.dcdc2_low()
+        }
+    }
+
+    /// Sets the output voltage for DCDC3 selected when the DEFDCDC3 pin is LOW
+    pub fn set_dcdc3_low(&mut self, voltage: DCDCVoltage) -> Tps6507xResult<(), E> {
+        let mut reg = dcdc::DEFDCDC3_LOW(0x00);
+        reg.set_dcdc3(voltage);
+        self.write_register(Registers::DEFDCDC3_LOW, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets the output voltage for DCDC3 selected when the DEFDCDC3 pin is LOW
+    pub fn dcdc3_low(&mut self) -> Tps6507xResult<DCDCVoltage, E> {
+        let reg = self.read_register_raw(Registers::DEFDCDC3_LOW)?;
+        Ok(dcdc::DEFDCDC3_LOW(reg).dcdc3())
+    }
+
     /// The output voltage for DCDC3 is switched between the value defined in DEFDCDC3_LOW and
     /// DEFDCDC3_HIGH depending on the status of the DEFDCDC3 pin. If DEFDCDC3 is LOW the value in
     /// DEFDCDC3_LOW is selected, if DEFDCDC3 = HIGH, the value in DEFDCDC3_HIGH is selected.
     pub fn set_dcdc3_high(&mut self, voltage: DCDCVoltage) -> Tps6507xResult<(), E> {
-        self.write_register_raw(Registers::DEFDCDC3_HIGH, voltage as u8)?;
+        let mut reg = dcdc::DEFDCDC3_HIGH(self.read_register_raw(Registers::DEFDCDC3_HIGH)?);
+        reg.set_dcdc3(voltage);
+        self.write_register(Registers::DEFDCDC3_HIGH, reg.0)?;
         Ok(())
     }
 
+    /// Gets the output voltage for DCDC3 selected when the DEFDCDC3 pin is HIGH
+    pub fn dcdc3_high(&mut self) -> Tps6507xResult<DCDCVoltage, E> {
+        let reg = self.read_register_raw(Registers::DEFDCDC3_HIGH)?;
+        Ok(dcdc::DEFDCDC3_HIGH(reg).dcdc3())
+    }
+
+    /// Gets the DCDC3 output voltage currently selected by the DEFDCDC3 pin state
+    pub fn dcdc3_active(&mut self, defdcdc3_high: bool) -> Tps6507xResult<DCDCVoltage, E> {
+        if defdcdc3_high {
+            self.dcdc3_high()
+        } else {
+            self.dcdc3_low()
+        }
+    }
+
+    /// Selects between the internal register setpoint and an external resistor divider for the
+    /// converter output voltage
+    ///
+    /// For DCDC2 and DCDC3 the mode is stored in DEFDCDC2_HIGH and DEFDCDC3_HIGH registers.
+    pub fn set_dcdc_adjust(
+        &mut self,
+        converter: dcdc::Dcdc,
+        adjust: dcdc::VoltageAdjust,
+    ) -> Tps6507xResult<(), E> {
+        match converter {
+            dcdc::Dcdc::Dcdc1 => {
+                let mut reg = dcdc::DEFDCDC1(self.read_register_raw(Registers::DEFDCDC1)?);
+                reg.set_extadj(adjust.into());
+                self.write_register(Registers::DEFDCDC1, reg.0)?;
+            }
+            dcdc::Dcdc::Dcdc2 => {
+                let mut reg =
+                    dcdc::DEFDCDC2_HIGH(self.read_register_raw(Registers::DEFDCDC2_HIGH)?);
+                reg.set_extadj(adjust.into());
+                self.write_register(Registers::DEFDCDC2_HIGH, reg.0)?;
+            }
+            dcdc::Dcdc::Dcdc3 => {
+                let mut reg =
+                    dcdc::DEFDCDC3_HIGH(self.read_register_raw(Registers::DEFDCDC3_HIGH)?);
+                reg.set_extadj(adjust.into());
+                self.write_register(Registers::DEFDCDC3_HIGH, reg.0)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the converter output voltage adjustment mode
+    pub fn dcdc_adjust(&mut self, converter: dcdc::Dcdc) -> Tps6507xResult<dcdc::VoltageAdjust, E> {
+        let extadj = match converter {
+            dcdc::Dcdc::Dcdc1 => {
+                dcdc::DEFDCDC1(self.read_register_raw(Registers::DEFDCDC1)?).extadj()
+            }
+            dcdc::Dcdc::Dcdc2 => {
+                dcdc::DEFDCDC2_HIGH(self.read_register_raw(Registers::DEFDCDC2_HIGH)?).extadj()
+            }
+            dcdc::Dcdc::Dcdc3 => {
+                dcdc::DEFDCDC3_HIGH(self.read_register_raw(Registers::DEFDCDC3_HIGH)?).extadj()
+            }
+        };
+        Ok(extadj.into())
+    }
+
+    /// Sets the slew rate of DCDC2 and DCDC3 output voltage changes, also used by LDO2 in
+    /// tracking mode
+    pub fn set_dvs_slew_rate(&mut self, rate: dcdc::SlewRate) -> Tps6507xResult<(), E> {
+        let mut reg = dcdc::DEFSLEW(0x00);
+        reg.set_slew(rate);
+        self.write_register(Registers::DEFSLEW, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets the slew rate of DCDC2 and DCDC3 output voltage changes
+    pub fn dvs_slew_rate(&mut self) -> Tps6507xResult<dcdc::SlewRate, E> {
+        let reg = self.read_register_raw(Registers::DEFSLEW)?;
+        Ok(dcdc::DEFSLEW(reg).slew())
+    }
+
     /// Sets output voltage of LDO1
     pub fn set_ldo1(&mut self, voltage: LDO1Voltage) -> Tps6507xResult<(), E> {
         let mut reg = ldo::LDO_CTRL1(self.read_register_raw(Registers::LDO_CTRL1)?);
         reg.set_ldo1(voltage);
 
-        self.write_register_raw(Registers::LDO_CTRL1, reg.0)?;
+        self.write_register(Registers::LDO_CTRL1, reg.0)?;
         Ok(())
     }
 
+    /// Gets output voltage of LDO1
+    pub fn ldo1(&mut self) -> Tps6507xResult<LDO1Voltage, E> {
+        let reg = self.read_register_raw(Registers::LDO_CTRL1)?;
+        Ok(ldo::LDO_CTRL1(reg).ldo1())
+    }
+
     /// The DEFLDO2 register is used to set the output voltage of LDO2
     pub fn set_ldo2(&mut self, voltage: DCDCVoltage) -> Tps6507xResult<(), E> {
-        self.write_register_raw(Registers::DEFLDO2, voltage as u8)?;
+        self.write_register(Registers::DEFLDO2, voltage as u8)?;
         Ok(())
     }
 
+    /// Gets output voltage of LDO2
+    pub fn ldo2(&mut self) -> Tps6507xResult<DCDCVoltage, E> {
+        let reg = self.read_register_raw(Registers::DEFLDO2)?;
+        Ok(ldo::DEFLDO2(reg).ldo2())
+    }
+
     /// Asserts/deasserts reference voltage LDO (pin BYPASS) for ADC
     pub fn set_adc_vref(&mut self, enable: bool) -> Tps6507xResult<(), E> {
         let mut reg = adc::ADCONFIG(self.read_register_raw(Registers::ADCONFIG)?);
         reg.set_vref_enable(enable);
-        self.write_register_raw(Registers::ADCONFIG, reg.0)?;
+        self.write_register(Registers::ADCONFIG, reg.0)?;
+        Ok(())
+    }
+
+    /// Get reference voltage LDO (pin BYPASS) enable flag
+    pub fn adc_vref(&mut self) -> Tps6507xResult<bool, E> {
+        let reg = self.read_register_raw(Registers::ADCONFIG)?;
+        Ok(adc::ADCONFIG(reg).vref_enable())
+    }
+
+    /// Sets touch screen controller mode
+    pub fn set_touch_screen_mode(
+        &mut self,
+        mode: tscmode::TouchScreenMode,
+    ) -> Tps6507xResult<(), E> {
+        let mut reg = tscmode::TSCMODE(0x00);
+        reg.set_mode(mode);
+        self.write_register(Registers::TSCMODE, reg.0)?;
+        Ok(())
+    }
+
+    /// Get touch screen controller mode
+    pub fn touch_screen_mode(&mut self) -> Tps6507xResult<tscmode::TouchScreenMode, E> {
+        let reg = self.read_register_raw(Registers::TSCMODE)?;
+        Ok(tscmode::TSCMODE(reg).mode())
+    }
+
+    /// Puts the touch screen controller into standby, so a touch sets TSC INT
+    ///
+    /// Powers the ADC, selects AD_IN14 and clears the spurious TSC INT raised by the mode change.
+    /// Other interrupt events latched in the meantime are returned, as reading INT clears them.
+    /// The INT pin is only pulled low on touch if `touch_screen` is set with [`Self::set_interrupt_mask`].
+    /// Fails with [`Tps6507xError::AdcBusy`] while a conversion is pending.
+    pub fn enable_touch_detect(&mut self) -> Tps6507xResult<int::InterruptEvents, E> {
+        if self.adc_pending.is_some() {
+            return Err(Tps6507xError::AdcBusy);
+        }
+        self.set_touch_screen_mode(tscmode::TouchScreenMode::Standby)?;
+
+        let mut reg = adc::ADCONFIG(self.read_register_raw(Registers::ADCONFIG)?);
+        reg.set_ad_enable(true);
+        reg.set_conversion_start(false);
+        reg.set_input_select(adc::AdcInputSelect::TouchScreenAllFunc);
+        self.write_register(Registers::ADCONFIG, reg.0)?;
+
+        let mut events = self.service_interrupt()?;
+        events.touch_screen = false;
+        Ok(events)
+    }
+
+    /// Returns `true` if a touch has been detected since INT was read last time
+    ///
+    /// Reading INT clears all latched events, the other events are returned alongside the flag.
+    pub fn pen_down(&mut self) -> Tps6507xResult<(bool, int::InterruptEvents), E> {
+        let mut events = self.service_interrupt()?;
+        let touch = events.touch_screen;
+        events.touch_screen = false;
+        Ok((touch, events))
+    }
+
+    /// Measures X-position, Y-position and pressure, then returns to touch screen standby
+    ///
+    /// Each conversion is polled every [`ADC_POLL_INTERVAL_US`] until `timeout_us` runs out.
+    pub fn read_touch<D: DelayUs<u16>>(
+        &mut self,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<tscmode::TouchSample, E> {
+        use tscmode::TouchScreenMode;
+
+        let vref = self.adc_vref()?;
+        let mut measure = |tps: &mut Self, mode| {
+            tps.set_touch_screen_mode(mode)?;
+            tps.convert(
+                adc::AdcInputSelect::TouchScreenAllFunc,
+                vref,
+                delay,
+                timeout_us,
+            )
+        };
+
+        let x = measure(self, TouchScreenMode::XPosition)?;
+        let y = measure(self, TouchScreenMode::YPosition)?;
+        let pressure = measure(self, TouchScreenMode::Pressure)?;
+        self.set_touch_screen_mode(TouchScreenMode::Standby)?;
+
+        Ok(tscmode::TouchSample { x, y, pressure })
+    }
+
+    /// Non-blocking conversion of an input selected at runtime
+    ///
+    /// The first call starts the conversion, following calls return `WouldBlock` until END OF
+    /// CONVERSION is set and then the result. Reading another input while a conversion is pending
+    /// fails with `Tps6507xError::AdcBusy`.
+    pub fn read_adc(
+        &mut self,
+        input: adc::AdcInputSelect,
+        vref: bool,
+    ) -> nb::Result<u16, Tps6507xError<E>> {
+        match self.adc_pending {
+            None => {
+                self.start_conversion(input, vref)?;
+                Err(nb::Error::WouldBlock)
+            }
+            Some(pending) if pending != input => Err(nb::Error::Other(Tps6507xError::AdcBusy)),
+            Some(_) => match self.conversion_result()? {
+                Some(sample) => Ok(sample),
+                None => Err(nb::Error::WouldBlock),
+            },
+        }
+    }
+
+    /// Converts `channel` and polls every [`ADC_POLL_INTERVAL_US`] until `timeout_us` runs out
+    ///
+    /// Fails with `Tps6507xError::AdcBusy` if a non-blocking conversion is pending.
+    pub fn read_adc_blocking<CH, D>(
+        &mut self,
+        _channel: &mut CH,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<u16, E>
+    where
+        CH: Channel<Self, ID = adc::AdcInputSelect> + VrefParam,
+        D: DelayUs<u16>,
+    {
+        self.read_adc_input_blocking(CH::channel(), CH::vref(), delay, timeout_us)
+    }
+
+    /// Blocking conversion of an input selected at runtime, see [`Self::read_adc_blocking`]
+    pub fn read_adc_input_blocking<D: DelayUs<u16>>(
+        &mut self,
+        input: adc::AdcInputSelect,
+        vref: bool,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<u16, E> {
+        self.convert(input, vref, delay, timeout_us)
+    }
+
+    /// Measures battery pack temperature with the thermistor selected by SENSOR TYPE
+    ///
+    /// Returns `None` if no NTC is detected at the TS pin.
+    pub fn battery_temperature<D: DelayUs<u16>>(
+        &mut self,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<Option<f32>, E> {
+        let sensor = self.charger_config()?.sensor_type;
+        self.battery_temperature_with(&Thermistor::for_sensor(sensor), delay, timeout_us)
+    }
+
+    /// Measures battery pack temperature with a custom bias network and NTC characteristic
+    pub fn battery_temperature_with<D: DelayUs<u16>>(
+        &mut self,
+        thermistor: &Thermistor,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<Option<f32>, E> {
+        let input = adc::AdcInputSelect::VoltageTsPin;
+        let vref = self.adc_vref()?;
+        let raw = self.convert(input, vref, delay, timeout_us)?;
+        Ok(thermistor.celsius(adc::millivolts(raw, input.full_scale_mv())))
+    }
+
+    /// Turns on the wLED current sinks and boost converter
+    pub fn set_backlight_enable(&mut self, enable: bool) -> Tps6507xResult<(), E> {
+        let mut reg = wled::WLED_CTRL1(self.read_register_raw(Registers::WLED_CTRL1)?);
+        reg.set_isink_enable(enable);
+        self.write_register(Registers::WLED_CTRL1, reg.0)?;
+        Ok(())
+    }
+
+    /// Get wLED current sinks enable flag
+    pub fn backlight_enabled(&mut self) -> Tps6507xResult<bool, E> {
+        let reg = self.read_register_raw(Registers::WLED_CTRL1)?;
+        Ok(wled::WLED_CTRL1(reg).isink_enable())
+    }
+
+    /// Sets PWM dimming frequency of the wLED current sinks
+    pub fn set_backlight_frequency(
+        &mut self,
+        frequency: wled::DimmingFrequency,
+    ) -> Tps6507xResult<(), E> {
+        let mut reg = wled::WLED_CTRL1(self.read_register_raw(Registers::WLED_CTRL1)?);
+        reg.set_dimming_frequency(frequency);
+        self.write_register(Registers::WLED_CTRL1, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets PWM dimming frequency of the wLED current sinks
+    pub fn backlight_frequency(&mut self) -> Tps6507xResult<wled::DimmingFrequency, E> {
+        let reg = self.read_register_raw(Registers::WLED_CTRL1)?;
+        Ok(wled::WLED_CTRL1(reg).dimming_frequency())
+    }
+
+    /// Selects the resistor defining the wLED current sinks current
+    pub fn set_backlight_current(&mut self, level: wled::CurrentLevel) -> Tps6507xResult<(), E> {
+        let mut reg = wled::WLED_CTRL2(self.read_register_raw(Registers::WLED_CTRL2)?);
+        reg.set_current_level(level);
+        self.write_register(Registers::WLED_CTRL2, reg.0)?;
         Ok(())
     }
-    
-    /// Raw register write access
+
+    /// Gets the resistor defining the wLED current sinks current
+    pub fn backlight_current(&mut self) -> Tps6507xResult<wled::CurrentLevel, E> {
+        let reg = self.read_register_raw(Registers::WLED_CTRL2)?;
+        Ok(wled::WLED_CTRL2(reg).current_level())
+    }
+
+    /// Sets backlight brightness in percent as PWM dimming duty cycle, clamped to 100
+    pub fn set_backlight_brightness(&mut self, percent: u8) -> Tps6507xResult<(), E> {
+        let mut reg = wled::WLED_CTRL2(self.read_register_raw(Registers::WLED_CTRL2)?);
+        reg.set_duty(wled::duty_from_percent(percent));
+        self.write_register(Registers::WLED_CTRL2, reg.0)?;
+        Ok(())
+    }
+
+    /// Gets backlight brightness in percent
+    pub fn backlight_brightness(&mut self) -> Tps6507xResult<u8, E> {
+        let reg = self.read_register_raw(Registers::WLED_CTRL2)?;
+        Ok(wled::percent_from_duty(wled::WLED_CTRL2(reg).duty()))
+    }
+
+    /// Borrows the driver as a backlight handle implementing `embedded_hal::PwmPin`
+    pub fn backlight(&mut self) -> Tps6507xResult<Backlight<'_, I2C>, E> {
+        Backlight::new(self)
+    }
+
+    /// Starts a conversion and polls END OF CONVERSION, returns the 10 bit result
+    fn convert<D: DelayUs<u16>>(
+        &mut self,
+        input: adc::AdcInputSelect,
+        vref: bool,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<u16, E> {
+        if self.adc_pending.is_some() {
+            return Err(Tps6507xError::AdcBusy);
+        }
+        self.start_conversion(input, vref)?;
+
+        let mut elapsed_us = 0;
+        loop {
+            match self.conversion_result() {
+                Ok(Some(sample)) => return Ok(sample),
+                Ok(None) => {}
+                Err(e) => {
+                    self.adc_pending = None;
+                    return Err(e);
+                }
+            }
+            if elapsed_us >= timeout_us {
+                self.adc_pending = None;
+                return Err(Tps6507xError::Timeout);
+            }
+            delay.delay_us(ADC_POLL_INTERVAL_US);
+            elapsed_us += u32::from(ADC_POLL_INTERVAL_US);
+        }
+    }
+
+    /// Forgets the pending conversion, so a conversion of another input can be started
+    ///
+    /// The result of the abandoned conversion is never returned.
+    pub fn cancel_conversion(&mut self) {
+        self.adc_pending = None;
+    }
+
+    /// Powers the ADC, selects `input` and starts a conversion
+    fn start_conversion(
+        &mut self,
+        input: adc::AdcInputSelect,
+        vref: bool,
+    ) -> Tps6507xResult<(), E> {
+        let mut reg = adc::ADCONFIG(0x00);
+        reg.set_ad_enable(true);
+        reg.set_conversion_start(true);
+        reg.set_vref_enable(vref);
+        reg.set_input_select(input);
+        self.write_register(Registers::ADCONFIG, reg.0)?;
+        self.adc_pending = Some(input);
+        Ok(())
+    }
+
+    /// Returns the 10 bit result of the pending conversion once END OF CONVERSION is set
+    fn conversion_result(&mut self) -> Tps6507xResult<Option<u16>, E> {
+        let reg = adc::ADCONFIG(self.read_register_raw(Registers::ADCONFIG)?);
+        if !reg.end_of_conversion() {
+            return Ok(None);
+        }
+
+        let lowb = self.read_register_raw(Registers::ADRESULT_1)?;
+        let highb = self.read_register_raw(Registers::ADRESULT_2)?;
+        self.adc_pending = None;
+        Ok(Some(lowb as u16 | (((highb & 0x03) as u16) << 8)))
+    }
+
+    fn write_register(&mut self, register: Registers, value: u8) -> Tps6507xResult<(), E> {
+        if self.verify_writes {
+            self.write_register_verified(register, value)
+        } else {
+            self.write_register_raw(register, value)?;
+            Ok(())
+        }
+    }
+
+    /// Register write followed by read back of the written value
+    ///
+    /// Read only and self clearing bits of the register are not compared. Registers without any
+    /// verifiable bit, like INT which clears latched events when read, are not read back.
+    pub fn write_register_verified(
+        &mut self,
+        register: Registers,
+        value: u8,
+    ) -> Tps6507xResult<(), E> {
+        self.write_register_raw(register, value)?;
+        let mask = register.verify_mask();
+        if mask == 0 {
+            return Ok(());
+        }
+        let actual = self.read_register_raw(register)?;
+        if (actual ^ value) & mask != 0 {
+            return Err(Tps6507xError::ReadBackMismatch {
+                register,
+                expected: value & mask,
+                actual: actual & mask,
+            });
+        }
+        Ok(())
+    }
+
+    /// Raw register write access, never verified
     pub fn write_register_raw(&mut self, register: Registers, value: u8) -> Result<(), E> {
-        self.i2c
-            .write(SLAVE_ADDR, &[register as u8, value])
+        self.i2c.write(SLAVE_ADDR, &[register as u8, value])
     }
 
     /// Raw register read access
@@ -136,14 +903,3 @@ impl<E> From<E> for Tps6507xError<E> {
         Self::Interface(e)
     }
 }
-
-// Hack impl to prevent nested `nb::Error<nb::Error<E>>` error types,
-// due to unfourtunate adc::OneShot trait interface
-impl<E> From<nb::Error<nb::Error<E>>> for Tps6507xError<nb::Error<E>> {
-    fn from(e: nb::Error<nb::Error<E>>) -> Self {
-        match e {
-            nb::Error::WouldBlock => Self::Interface(nb::Error::WouldBlock),
-            nb::Error::Other(nb) => Self::Interface(nb),
-        }
-    }
-}
@@ -0,0 +1,204 @@
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::i2c;
+
+use crate::device::{Tps6507x, Tps6507xResult};
+use crate::regs::adc::AdcInputSelect;
+use crate::regs::chgconfig::ChargeState;
+
+/// Point of an open circuit voltage to state of charge table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OcvPoint {
+    pub millivolts: u32,
+    pub percent: u8,
+}
+
+impl OcvPoint {
+    pub const fn new(millivolts: u32, percent: u8) -> Self {
+        Self {
+            millivolts,
+            percent,
+        }
+    }
+}
+
+/// Typical open circuit voltage of a 4.2 V Li-Ion or Li-Po cell at room temperature
+pub const LI_ION_OCV: [OcvPoint; 12] = [
+    OcvPoint::new(3000, 0),
+    OcvPoint::new(3450, 5),
+    OcvPoint::new(3680, 10),
+    OcvPoint::new(3740, 20),
+    OcvPoint::new(3770, 30),
+    OcvPoint::new(3790, 40),
+    OcvPoint::new(3820, 50),
+    OcvPoint::new(3870, 60),
+    OcvPoint::new(3920, 70),
+    OcvPoint::new(3980, 80),
+    OcvPoint::new(4060, 90),
+    OcvPoint::new(4200, 100),
+];
+
+/// Battery level classification
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatteryLevel {
+    Normal,
+    /// State of charge is at or below the low threshold
+    Low,
+    /// State of charge is at or below the critical threshold, the system should shut down
+    Critical,
+}
+
+/// State of charge estimator settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuelGaugeConfig {
+    /// Points sorted by rising voltage, interpolated linearly and clamped at the ends
+    pub ocv_table: &'static [OcvPoint],
+    /// Battery voltage rise above open circuit voltage while charging
+    pub charge_offset_mv: u32,
+    /// Battery voltage drop below open circuit voltage while the system runs from the battery
+    pub discharge_offset_mv: u32,
+    /// Low pass filter strength, each reading is weighted with 1 / 2^`filter_shift`
+    pub filter_shift: u8,
+    pub low_percent: u8,
+    pub critical_percent: u8,
+}
+
+impl Default for FuelGaugeConfig {
+    fn default() -> Self {
+        Self {
+            ocv_table: &LI_ION_OCV,
+            charge_offset_mv: 100,
+            discharge_offset_mv: 50,
+            filter_shift: 3,
+            low_percent: 15,
+            critical_percent: 5,
+        }
+    }
+}
+
+/// Estimated battery state
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StateOfCharge {
+    pub percent: u8,
+    pub level: BatteryLevel,
+    /// Filtered open circuit voltage estimate
+    pub ocv_mv: u32,
+}
+
+/// Battery state of charge estimator based on the battery voltage
+///
+/// Battery voltage is corrected by the expected charge or load offset, filtered and looked up in
+/// the open circuit voltage table. While running from the battery the reported percentage never
+/// rises, so it does not follow the voltage recovery after load peaks.
+#[derive(Debug, Clone)]
+pub struct FuelGauge {
+    config: FuelGaugeConfig,
+    /// Filtered voltage in microvolts
+    filtered_uv: Option<u32>,
+    percent: Option<u8>,
+}
+
+impl FuelGauge {
+    pub fn new(config: FuelGaugeConfig) -> Self {
+        Self {
+            config,
+            filtered_uv: None,
+            percent: None,
+        }
+    }
+
+    pub fn config(&self) -> &FuelGaugeConfig {
+        &self.config
+    }
+
+    /// Forgets filter state, e.g. after a battery swap
+    pub fn reset(&mut self) {
+        self.filtered_uv = None;
+        self.percent = None;
+    }
+
+    /// Feeds a battery voltage reading taken in charger `state`
+    pub fn update(&mut self, battery_mv: u32, state: ChargeState) -> StateOfCharge {
+        let ocv_mv = match state {
+            ChargeState::Charging => battery_mv.saturating_sub(self.config.charge_offset_mv),
+            ChargeState::NoSource => battery_mv + self.config.discharge_offset_mv,
+            _ => battery_mv,
+        };
+
+        let sample_uv = ocv_mv * 1000;
+        let filtered_uv = match self.filtered_uv {
+            Some(filtered) => {
+                let shift = self.config.filter_shift.min(16);
+                let delta = (sample_uv as i64 - filtered as i64) >> shift;
+                (filtered as i64 + delta) as u32
+            }
+            None => sample_uv,
+        };
+        self.filtered_uv = Some(filtered_uv);
+
+        let ocv_mv = (filtered_uv + 500) / 1000;
+        let mut percent = self.lookup(ocv_mv);
+        if let (ChargeState::NoSource, Some(last)) = (state, self.percent) {
+            percent = percent.min(last);
+        }
+        self.percent = Some(percent);
+
+        let level = if percent <= self.config.critical_percent {
+            BatteryLevel::Critical
+        } else if percent <= self.config.low_percent {
+            BatteryLevel::Low
+        } else {
+            BatteryLevel::Normal
+        };
+
+        StateOfCharge {
+            percent,
+            level,
+            ocv_mv,
+        }
+    }
+
+    /// Measures battery voltage and charger state and feeds them to [`Self::update`]
+    pub fn measure<I2C, E, D>(
+        &mut self,
+        tps: &mut Tps6507x<I2C>,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<StateOfCharge, E>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        D: DelayUs<u16>,
+    {
+        let input = AdcInputSelect::VoltageBatPins;
+        let vref = tps.adc_vref()?;
+        let raw = tps.read_adc_input_blocking(input, vref, delay, timeout_us)?;
+        let state = tps.charger_status()?.state();
+        Ok(self.update(input.millivolts(raw), state))
+    }
+
+    fn lookup(&self, ocv_mv: u32) -> u8 {
+        let table = self.config.ocv_table;
+        let (first, last) = match (table.first(), table.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return 0,
+        };
+        if ocv_mv <= first.millivolts {
+            return first.percent;
+        }
+        for pair in table.windows(2) {
+            let (low, high) = (pair[0], pair[1]);
+            if ocv_mv <= high.millivolts {
+                let span_mv = high.millivolts - low.millivolts;
+                let span = high.percent.saturating_sub(low.percent) as u32;
+                let offset = ((ocv_mv - low.millivolts) * span + span_mv / 2) / span_mv;
+                return low.percent + offset as u8;
+            }
+        }
+        last.percent
+    }
+}
+
+impl Default for FuelGauge {
+    fn default() -> Self {
+        Self::new(FuelGaugeConfig::default())
+    }
+}
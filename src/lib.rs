@@ -11,15 +11,44 @@
 //!
 #![no_std]
 
+mod backlight;
 mod defs;
 mod device;
+mod fuel_gauge;
+mod ntc;
 mod oneshot;
 mod regs;
+mod scan;
+mod touch;
 
+pub use backlight::{gamma_correct, Backlight, BacklightFade, Easing};
 pub use defs::*;
-pub use device::{Tps6507x, Tps6507xError, Tps6507xResult, SLAVE_ADDR};
-pub use regs::chgconfig::{ChargerConfig, SafetyTimerTimeOut, SensorType};
+pub use device::{Tps6507x, Tps6507xError, Tps6507xResult, ADC_POLL_INTERVAL_US, SLAVE_ADDR};
+pub use fuel_gauge::{
+    BatteryLevel, FuelGauge, FuelGaugeConfig, OcvPoint, StateOfCharge, LI_ION_OCV,
+};
+pub use ntc::{BiasNetwork, NtcModel, NtcPoint, Thermistor, V_NO_NTC_MV};
+pub use regs::adc::AdcInputSelect;
+pub use regs::chgconfig::{
+    ChargeState, ChargeVoltage, ChargerConfig, ChargerConfig2, ChargerConfig3, ChargerStatus,
+    DppmThreshold, PrechargeTime, PrechargeVoltage, SafetyTimerTimeOut, SensorType,
+    TerminationCurrent,
+};
+pub use regs::ctrl::{
+    ControlConfig, DcdcSequence, Rail, RailSet, SequenceMode, UvloHysteresis, UvloThreshold,
+};
+pub use regs::dcdc::{Dcdc, SlewRate, VoltageAdjust};
+pub use regs::int::{InterruptEvents, InterruptMask};
+pub use regs::ldo::LdoSequence;
+pub use regs::pgood::{PowerGood, PowerGoodDelay, PowerGoodMask};
 pub use regs::ppath::{AcInputCurrent, PowerPath, UsbInputCurrent};
+pub use regs::tscmode::{TouchSample, TouchScreenMode};
+pub use regs::wled::{CurrentLevel, DimmingFrequency};
+pub use regs::Registers;
+pub use scan::{AdcScan, ScanChannel, ScanResult};
+pub use touch::{
+    Calibration, Filter, PenEvent, Point, TouchConfig, TouchScreen, MAX_TOUCH_SAMPLES,
+};
 
 pub mod channel {
     pub use crate::oneshot::channel::*;
@@ -0,0 +1,157 @@
+use crate::regs::chgconfig::SensorType;
+
+/// 0 °C in kelvin
+const ZERO_CELSIUS_K: f32 = 273.15;
+
+/// TS pin voltage at and above which the device reports no NTC connected (V noNTC)
+pub const V_NO_NTC_MV: u32 = 2000;
+
+/// Resistor network between the internal LDO, the TS pin and the battery pack NTC
+///
+/// The NTC is linearized by a resistor in parallel, the combination is pulled up to the bias voltage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiasNetwork {
+    /// Internal LDO voltage the network is connected to
+    pub bias_mv: u32,
+    /// Internal pullup resistor from TS to the bias voltage
+    pub pullup_ohms: u32,
+    /// External linearization resistor in parallel to the NTC
+    pub parallel_ohms: Option<u32>,
+}
+
+impl BiasNetwork {
+    /// Network recommended by the datasheet for the NTC selected with SENSOR TYPE
+    ///
+    /// RT2 is taken from datasheet Table 15. The pullup is fitted to the VCOLD and VHOT trip points
+    /// with the Table 15 NTC, within the 7.35 kΩ and 62.5 kΩ ±2% of the electrical characteristics.
+    pub fn for_sensor(sensor: SensorType) -> Self {
+        let (pullup_ohms, parallel_ohms) = match sensor {
+            SensorType::V10K => (7_360, 75_000),
+            SensorType::V100K => (62_730, 370_000),
+        };
+        Self {
+            bias_mv: 2250,
+            pullup_ohms,
+            parallel_ohms: Some(parallel_ohms),
+        }
+    }
+
+    /// NTC resistance for a TS pin voltage, `None` if the voltage means open or shorted NTC
+    ///
+    /// Voltages at or above [`V_NO_NTC_MV`] are treated as open, like the charger does.
+    pub fn ntc_ohms(&self, ts_mv: u32) -> Option<f32> {
+        if ts_mv == 0 || ts_mv >= self.bias_mv || ts_mv >= V_NO_NTC_MV {
+            return None;
+        }
+        let network = self.pullup_ohms as f32 * ts_mv as f32 / (self.bias_mv - ts_mv) as f32;
+        match self.parallel_ohms {
+            Some(parallel) if network >= parallel as f32 => None,
+            Some(parallel) => Some(network * parallel as f32 / (parallel as f32 - network)),
+            None => Some(network),
+        }
+    }
+}
+
+impl From<SensorType> for BiasNetwork {
+    fn from(v: SensorType) -> Self {
+        Self::for_sensor(v)
+    }
+}
+
+/// Point of a thermistor resistance table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NtcPoint {
+    pub celsius: f32,
+    pub ohms: u32,
+}
+
+/// Resistance to temperature characteristic of a thermistor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NtcModel {
+    /// Beta parameter equation, `r25_ohms` is the resistance at 25 °C
+    Beta { r25_ohms: f32, beta: f32 },
+    /// Steinhart-Hart equation, 1/T = a + b ln(R) + c ln(R)^3
+    SteinhartHart { a: f32, b: f32, c: f32 },
+    /// Points sorted by rising temperature, interpolated linearly and clamped at the ends
+    Table(&'static [NtcPoint]),
+}
+
+impl NtcModel {
+    /// Beta model of the NTC selected with SENSOR TYPE, datasheet Table 15 10 kΩ curve 2 or
+    /// 100 kΩ curve 1
+    pub fn for_sensor(sensor: SensorType) -> Self {
+        match sensor {
+            SensorType::V10K => NtcModel::Beta {
+                r25_ohms: 10_000.0,
+                beta: 3477.0,
+            },
+            SensorType::V100K => NtcModel::Beta {
+                r25_ohms: 100_000.0,
+                beta: 3964.0,
+            },
+        }
+    }
+
+    /// Temperature in degrees Celsius for a thermistor resistance
+    pub fn celsius(&self, ohms: f32) -> f32 {
+        match *self {
+            NtcModel::Beta { r25_ohms, beta } => {
+                let inv_t = 1.0 / (ZERO_CELSIUS_K + 25.0) + libm::logf(ohms / r25_ohms) / beta;
+                1.0 / inv_t - ZERO_CELSIUS_K
+            }
+            NtcModel::SteinhartHart { a, b, c } => {
+                let ln_r = libm::logf(ohms);
+                1.0 / (a + b * ln_r + c * ln_r * ln_r * ln_r) - ZERO_CELSIUS_K
+            }
+            NtcModel::Table(points) => table_celsius(points, ohms),
+        }
+    }
+}
+
+fn table_celsius(points: &[NtcPoint], ohms: f32) -> f32 {
+    let (first, last) = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return f32::NAN,
+    };
+    if ohms >= first.ohms as f32 {
+        return first.celsius;
+    }
+    for pair in points.windows(2) {
+        let (hot, cold) = (pair[1], pair[0]);
+        if ohms >= hot.ohms as f32 {
+            let fraction = (cold.ohms as f32 - ohms) / (cold.ohms as f32 - hot.ohms as f32);
+            return cold.celsius + fraction * (hot.celsius - cold.celsius);
+        }
+    }
+    last.celsius
+}
+
+/// Battery pack temperature sensor, bias network and NTC characteristic
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thermistor {
+    pub bias: BiasNetwork,
+    pub model: NtcModel,
+}
+
+impl Thermistor {
+    /// Datasheet network and thermistor for the NTC selected with SENSOR TYPE
+    pub fn for_sensor(sensor: SensorType) -> Self {
+        Self {
+            bias: BiasNetwork::for_sensor(sensor),
+            model: NtcModel::for_sensor(sensor),
+        }
+    }
+
+    /// Temperature in degrees Celsius for a TS pin voltage, `None` if no NTC is detected
+    pub fn celsius(&self, ts_mv: u32) -> Option<f32> {
+        self.bias
+            .ntc_ohms(ts_mv)
+            .map(|ohms| self.model.celsius(ohms))
+    }
+}
+
+impl From<SensorType> for Thermistor {
+    fn from(v: SensorType) -> Self {
+        Self::for_sensor(v)
+    }
+}
@@ -1,34 +1,21 @@
+use core::num::NonZeroU32;
+
 use embedded_hal::adc;
 use embedded_hal::blocking::i2c;
 
-use crate::device::Tps6507x;
-use crate::regs::{self, Registers};
+use crate::device::{Tps6507x, Tps6507xError};
+use crate::regs;
 
+/// Two phase conversion of `CH`, see [`Tps6507x::read_adc`]
 impl<I2C, E, CH> adc::OneShot<Tps6507x<I2C>, u16, CH> for Tps6507x<I2C>
 where
     I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
     CH: adc::Channel<Tps6507x<I2C>, ID = regs::adc::AdcInputSelect> + channel::VrefParam,
 {
-    type Error = E;
+    type Error = Tps6507xError<E>;
 
     fn read(&mut self, _: &mut CH) -> nb::Result<u16, Self::Error> {
-        let mut reg = regs::adc::ADCONFIG(self.read_register_raw(Registers::ADCONFIG)?);
-
-        if !reg.end_of_conversion() {
-            return Err(nb::Error::WouldBlock);
-        }
-
-        reg.set_ad_enable(true);
-        reg.set_conversion_start(true);
-        reg.set_vref_enable(CH::vref());
-        reg.set_input_select(CH::channel());
-        reg.set_end_of_conversion(false);
-        self.write_register_raw(Registers::ADCONFIG, reg.0)?;
-
-        let lowb = self.read_register_raw(Registers::ADRESULT_1)?;
-        let highb = self.read_register_raw(Registers::ADRESULT_2)?;
-
-        Ok(lowb as u16 | ((highb as u16) << 8))
+        self.read_adc(CH::channel(), CH::vref())
     }
 }
 
@@ -43,6 +30,8 @@ pub mod channel {
     pub struct VoltageAdIn4;
     pub struct VoltageTsPin;
     pub struct VoltageIsetPin;
+    pub struct VoltageAcPin;
+    pub struct VoltageSysPin;
     pub struct InputVoltageCharger;
     pub struct VoltageBatPins;
     pub struct VoltageAdIn5;
@@ -57,6 +46,8 @@ pub mod channel {
     pub struct VoltageAdIn4_VrefOn;
     pub struct VoltageTsPin_VrefOn;
     pub struct VoltageIsetPin_VrefOn;
+    pub struct VoltageAcPin_VrefOn;
+    pub struct VoltageSysPin_VrefOn;
     pub struct InputVoltageCharger_VrefOn;
     pub struct VoltageBatPins_VrefOn;
     pub struct VoltageAdIn5_VrefOn;
@@ -69,6 +60,33 @@ pub mod channel {
         fn vref() -> bool;
     }
 
+    /// Conversion of raw results to the voltage at the channel input
+    ///
+    /// The ADC always uses the internal 2.26 V reference, `VrefParam::vref()` only keeps the BYPASS
+    /// output on, so `_VrefOn` channels scale the same as their plain counterparts.
+    pub trait Scale {
+        /// Input voltage giving the maximum reading, in millivolts
+        const FULL_SCALE_MV: u32;
+
+        /// Converts a raw result to millivolts
+        fn millivolts(raw: u16) -> u32 {
+            regs::adc::millivolts(raw, Self::FULL_SCALE_MV)
+        }
+    }
+
+    /// Typical fast charge current factor KISET in A·Ω, ICHG = KISET / RISET
+    ///
+    /// Datasheet range is 820 to 1080 A·Ω for 1500 mA and 890 to 1200 A·Ω for 100 mA.
+    pub const KISET_OHM_AMPS: u32 = 950;
+
+    /// Battery charge current in milliamps from a raw `VoltageIsetPin` result
+    ///
+    /// `riset_ohms` is the resistor connected from ISET to GND. Uses the datasheet ADC formula
+    /// Icharge = U_ISET / RSET × KISET with the typical [`KISET_OHM_AMPS`].
+    pub fn charge_current_ma(raw: u16, riset_ohms: NonZeroU32) -> u32 {
+        VoltageIsetPin::millivolts(raw) * KISET_OHM_AMPS / riset_ohms.get()
+    }
+
     macro_rules! impl_channel {
         (VREF_OFF, $CH:ident ) => {
             impl_channel!(INNER_IMPL, $CH, $CH);
@@ -96,6 +114,10 @@ pub mod channel {
                     Self::ID::$CH
                 }
             }
+
+            impl Scale for $TYNAME {
+                const FULL_SCALE_MV: u32 = regs::adc::AdcInputSelect::$CH.full_scale_mv();
+            }
         };
     }
 
@@ -105,6 +127,8 @@ pub mod channel {
     impl_channel!(VREF_OFF, VoltageAdIn4);
     impl_channel!(VREF_OFF, VoltageTsPin);
     impl_channel!(VREF_OFF, VoltageIsetPin);
+    impl_channel!(VREF_OFF, VoltageAcPin);
+    impl_channel!(VREF_OFF, VoltageSysPin);
     impl_channel!(VREF_OFF, InputVoltageCharger);
     impl_channel!(VREF_OFF, VoltageBatPins);
     impl_channel!(VREF_OFF, VoltageAdIn5);
@@ -119,6 +143,8 @@ pub mod channel {
     impl_channel!(VREF_ON, VoltageAdIn4_VrefOn, VoltageAdIn4);
     impl_channel!(VREF_ON, VoltageTsPin_VrefOn, VoltageTsPin);
     impl_channel!(VREF_ON, VoltageIsetPin_VrefOn, VoltageIsetPin);
+    impl_channel!(VREF_ON, VoltageAcPin_VrefOn, VoltageAcPin);
+    impl_channel!(VREF_ON, VoltageSysPin_VrefOn, VoltageSysPin);
     impl_channel!(VREF_ON, InputVoltageCharger_VrefOn, InputVoltageCharger);
     impl_channel!(VREF_ON, VoltageBatPins_VrefOn, VoltageBatPins);
     impl_channel!(VREF_ON, VoltageAdIn5_VrefOn, VoltageAdIn5);
@@ -1,4 +1,5 @@
 #![allow(non_camel_case_types)]
+#![allow(clippy::upper_case_acronyms)]
 
 use bitfield::bitfield;
 
@@ -15,6 +16,7 @@ pub mod ppath;
 pub mod tscmode;
 pub mod wled;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum Registers {
     /// Power Path Controls
@@ -69,10 +71,47 @@ pub enum Registers {
     WLED_CTRL2 = 0x19,
 }
 
+impl Registers {
+    /// Bits which keep written value, i.e. neither read only nor changed by the device itself
+    ///
+    /// INT has none, as reading it back would clear the latched interrupt events. Rail enable bits
+    /// of CON_CTRL1 are reset by the device while the matching enable pin is LOW.
+    pub(crate) fn verify_mask(self) -> u8 {
+        use Registers::*;
+        match self {
+            PPATH1 => 0b0011_1111,
+            INT => 0b0000_0000,
+            CHGCONFIG0 => 0b0000_0000,
+            CHGCONFIG1 => 0b1111_1111,
+            CHGCONFIG2 => 0b1111_0000,
+            CHGCONFIG3 => 0b1111_1101,
+            ADCONFIG => 0b1001_1111,
+            TSCMODE => 0b0000_0111,
+            ADRESULT_1 => 0b0000_0000,
+            ADRESULT_2 => 0b0000_0000,
+            PGOOD => 0b0110_0000,
+            PGOODMASK => 0b0011_1111,
+            CON_CTRL1 => 0b1110_0000,
+            CON_CTRL2 => 0b1111_1111,
+            CON_CTRL3 => 0b1111_1111,
+            DEFDCDC1 => 0b1011_1111,
+            DEFDCDC2_LOW => 0b0011_1111,
+            DEFDCDC2_HIGH => 0b1011_1111,
+            DEFDCDC3_LOW => 0b0011_1111,
+            DEFDCDC3_HIGH => 0b1011_1111,
+            DEFSLEW => 0b0000_0111,
+            LDO_CTRL1 => 0b1110_1111,
+            DEFLDO2 => 0b0111_1111,
+            WLED_CTRL1 => 0b1011_0000,
+            WLED_CTRL2 => 0b1111_1111,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::defs::DCDCVoltage;
-    use crate::regs::{chgconfig::*, ldo::*};
+    use crate::regs::{chgconfig::*, ctrl::*, dcdc::*, ldo::*};
 
     #[test]
     fn test_bitfield_ldo2() {
@@ -80,6 +119,47 @@ mod tests {
         assert_eq!(reg.0, 0x3F);
     }
 
+    #[test]
+    fn test_dcdc_voltage_millivolts() {
+        assert_eq!(DCDCVoltage::V0_725.millivolts(), 725);
+        assert_eq!(DCDCVoltage::V1_500.millivolts(), 1500);
+        assert_eq!(DCDCVoltage::V1_550.millivolts(), 1550);
+        assert_eq!(DCDCVoltage::V2_900.millivolts(), 2900);
+        assert_eq!(DCDCVoltage::V3_000.millivolts(), 3000);
+        assert_eq!(DCDCVoltage::V3_300.millivolts(), 3300);
+    }
+
+    #[test]
+    fn test_slew_settling_time() {
+        let rate = SlewRate::V0_9mVus;
+        assert_eq!(
+            rate.settling_time_us(DCDCVoltage::V1_000, DCDCVoltage::V1_200),
+            223
+        );
+        assert_eq!(
+            rate.settling_time_us(DCDCVoltage::V1_200, DCDCVoltage::V1_000),
+            223
+        );
+        let reg = DEFSLEW(0b0000_0111);
+        assert_eq!(
+            reg.slew()
+                .settling_time_us(DCDCVoltage::V1_000, DCDCVoltage::V3_300),
+            0
+        );
+    }
+
+    #[test]
+    fn test_bitfield_con_ctrl2() {
+        let reg: CON_CTRL2 = ControlConfig::default().into();
+        assert_eq!(reg.0, 0x0D);
+
+        let config: ControlConfig = CON_CTRL2(0b1000_0010).into();
+        assert!(config.timer_1s_enable && !config.timer_5s_enable);
+        assert!(!config.mask_en_dcdc3);
+        assert_eq!(config.uvlo_hysteresis, UvloHysteresis::V400mV);
+        assert_eq!(config.uvlo_threshold, UvloThreshold::V3_10);
+    }
+
     #[test]
     fn test_bitfield_chgconfig1() {
         let mut reg = CHGCONFIG1(0x00);
@@ -87,4 +167,28 @@ mod tests {
         reg.set_safety_timer_enable(true);
         assert_eq!(reg.0, 0x30);
     }
+
+    #[test]
+    fn test_bitfield_chgconfig2() {
+        let reg: CHGCONFIG2 = ChargerConfig2::default().into();
+        assert_eq!(reg.0, 0xE0);
+
+        let config: ChargerConfig2 = CHGCONFIG2(0x1F).into();
+        assert!(!config.dynamic_timer);
+        assert_eq!(config.precharge_voltage, PrechargeVoltage::V2_5);
+        assert_eq!(config.charge_voltage, ChargeVoltage::V4_15);
+    }
+
+    #[test]
+    fn test_bitfield_chgconfig3() {
+        let reg: CHGCONFIG3 = ChargerConfig3::default().into();
+        assert_eq!(reg.0, 0x64);
+
+        let config: ChargerConfig3 = CHGCONFIG3(0b1001_1011).into();
+        assert!(!config.ac_current_sink_enable);
+        assert!(!config.usb_current_sink_enable);
+        assert_eq!(config.dppm_threshold, DppmThreshold::V3_50);
+        assert_eq!(config.precharge_time, PrechargeTime::V60Min);
+        assert_eq!(config.termination_current, TerminationCurrent::V15Percent);
+    }
 }
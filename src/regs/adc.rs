@@ -1,6 +1,9 @@
+use core::convert::TryFrom;
+
 use super::*;
 
-#[derive(Debug)]
+/// ADC input multiplexer selection
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum AdcInputSelect {
     VoltageAdIn1 = 0b0000,
@@ -9,6 +12,8 @@ pub enum AdcInputSelect {
     VoltageAdIn4 = 0b0011,
     VoltageTsPin = 0b0100,
     VoltageIsetPin = 0b0101,
+    VoltageAcPin = 0b0110,
+    VoltageSysPin = 0b0111,
     InputVoltageCharger = 0b1000,
     VoltageBatPins = 0b1001,
     VoltageAdIn5 = 0b1010,
@@ -18,24 +23,55 @@ pub enum AdcInputSelect {
     TouchScreenXYPos = 0b1111,
 }
 
-impl From<u8> for AdcInputSelect {
-    fn from(v: u8) -> Self {
+impl AdcInputSelect {
+    /// Input voltage giving the maximum reading, in millivolts
+    pub const fn full_scale_mv(self) -> u32 {
+        use AdcInputSelect::*;
+        match self {
+            VoltageAdIn1 | VoltageAdIn2 | VoltageAdIn3 | VoltageAdIn4 | VoltageTsPin
+            | VoltageIsetPin | TouchScreenAllFunc | TouchScreenXYPos => 2250,
+            VoltageAcPin | VoltageSysPin | InputVoltageCharger | VoltageBatPins | VoltageAdIn5
+            | VoltageAdIn6 | VoltageAdIn7 => 6000,
+        }
+    }
+
+    /// Converts a raw result of this input to millivolts
+    pub fn millivolts(self, raw: u16) -> u32 {
+        millivolts(raw, self.full_scale_mv())
+    }
+}
+
+/// Number of ADC steps, 10 bit resolution
+pub(crate) const ADC_STEPS: u32 = 1024;
+
+/// Converts a raw ADC result to millivolts at the input, rounded to nearest
+pub(crate) fn millivolts(raw: u16, full_scale_mv: u32) -> u32 {
+    (raw as u32 * full_scale_mv + ADC_STEPS / 2) / ADC_STEPS
+}
+
+/// Fails with the rejected value for reserved selections
+impl TryFrom<u8> for AdcInputSelect {
+    type Error = u8;
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
         use AdcInputSelect::*;
         match v {
-            0b0000 => VoltageAdIn1,
-            0b0001 => VoltageAdIn2,
-            0b0010 => VoltageAdIn3,
-            0b0011 => VoltageAdIn4,
-            0b0100 => VoltageTsPin,
-            0b0101 => VoltageIsetPin,
-            0b1000 => InputVoltageCharger,
-            0b1001 => VoltageBatPins,
-            0b1010 => VoltageAdIn5,
-            0b1011 => VoltageAdIn6,
-            0b1100 => VoltageAdIn7,
-            0b1110 => TouchScreenAllFunc,
-            0b1111 => TouchScreenXYPos,
-            _ => unreachable!(),
+            0b0000 => Ok(VoltageAdIn1),
+            0b0001 => Ok(VoltageAdIn2),
+            0b0010 => Ok(VoltageAdIn3),
+            0b0011 => Ok(VoltageAdIn4),
+            0b0100 => Ok(VoltageTsPin),
+            0b0101 => Ok(VoltageIsetPin),
+            0b0110 => Ok(VoltageAcPin),
+            0b0111 => Ok(VoltageSysPin),
+            0b1000 => Ok(InputVoltageCharger),
+            0b1001 => Ok(VoltageBatPins),
+            0b1010 => Ok(VoltageAdIn5),
+            0b1011 => Ok(VoltageAdIn6),
+            0b1100 => Ok(VoltageAdIn7),
+            0b1110 => Ok(TouchScreenAllFunc),
+            0b1111 => Ok(TouchScreenXYPos),
+            _ => Err(v),
         }
     }
 }
@@ -46,12 +82,18 @@ impl From<AdcInputSelect> for u8 {
     }
 }
 
+impl ADCONFIG {
+    pub fn set_input_select(&mut self, input: AdcInputSelect) {
+        self.set_input_select_raw(input.into());
+    }
+}
+
 bitfield! {
     // 0x07
     pub struct ADCONFIG(u8);
     impl Debug;
 
-    pub from into AdcInputSelect, input_select, set_input_select: 3, 0;
+    pub input_select_raw, set_input_select_raw: 3, 0;
     pub vref_enable, set_vref_enable: 4;
     pub end_of_conversion, set_end_of_conversion: 5;
     pub conversion_start, set_conversion_start: 6;
@@ -1,4 +1,5 @@
 use super::*;
+use crate::regs::ppath::PPATH1;
 
 #[derive(Debug)]
 pub struct ChargerConfig {
@@ -40,7 +41,329 @@ impl From<ChargerConfig> for CHGCONFIG1 {
     }
 }
 
+impl From<CHGCONFIG1> for ChargerConfig {
+    fn from(reg: CHGCONFIG1) -> Self {
+        Self {
+            charger_enable: reg.charger_enable(),
+            suspend_charge: reg.suspend_charge(),
+            charge_termination: !reg.charge_termination_off(),
+            charger_reset: reg.charger_reset(),
+            sensor_type: reg.sensor_type10k().into(),
+            safety_timer_enable: reg.safety_timer_enable(),
+            charger_safety_timer_timeout: reg.charge_safety_timer(),
+        }
+    }
+}
+
 #[derive(Debug)]
+pub struct ChargerConfig2 {
+    pub dynamic_timer: bool,
+    pub precharge_voltage: PrechargeVoltage,
+    pub charge_voltage: ChargeVoltage,
+}
+
+impl Default for ChargerConfig2 {
+    fn default() -> Self {
+        Self {
+            dynamic_timer: true,
+            precharge_voltage: PrechargeVoltage::V2_9,
+            charge_voltage: ChargeVoltage::V4_20,
+        }
+    }
+}
+
+impl From<ChargerConfig2> for CHGCONFIG2 {
+    fn from(v: ChargerConfig2) -> Self {
+        let mut reg = CHGCONFIG2(0x00);
+        reg.set_dynamic_timer(v.dynamic_timer);
+        reg.set_precharge_voltage(v.precharge_voltage);
+        reg.set_charge_voltage(v.charge_voltage);
+
+        reg
+    }
+}
+
+impl From<CHGCONFIG2> for ChargerConfig2 {
+    fn from(reg: CHGCONFIG2) -> Self {
+        Self {
+            dynamic_timer: reg.dynamic_timer(),
+            precharge_voltage: reg.precharge_voltage(),
+            charge_voltage: reg.charge_voltage(),
+        }
+    }
+}
+
+/// Precharge to fast charge transition voltage
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum PrechargeVoltage {
+    V2_5 = 0b0,
+    V2_9 = 0b1,
+}
+
+impl From<u8> for PrechargeVoltage {
+    fn from(v: u8) -> Self {
+        use PrechargeVoltage::*;
+        match v {
+            0b0 => V2_5,
+            0b1 => V2_9,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<PrechargeVoltage> for u8 {
+    fn from(v: PrechargeVoltage) -> Self {
+        v as u8
+    }
+}
+
+/// Battery charge regulation voltage
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum ChargeVoltage {
+    V4_10 = 0b00,
+    V4_15 = 0b01,
+    V4_20 = 0b10,
+    V4_25 = 0b11,
+}
+
+impl From<u8> for ChargeVoltage {
+    fn from(v: u8) -> Self {
+        use ChargeVoltage::*;
+        match v {
+            0b00 => V4_10,
+            0b01 => V4_15,
+            0b10 => V4_20,
+            0b11 => V4_25,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<ChargeVoltage> for u8 {
+    fn from(v: ChargeVoltage) -> Self {
+        v as u8
+    }
+}
+
+#[derive(Debug)]
+pub struct ChargerConfig3 {
+    pub ac_current_sink_enable: bool,
+    pub dppm_threshold: DppmThreshold,
+    pub precharge_time: PrechargeTime,
+    pub termination_current: TerminationCurrent,
+    pub usb_current_sink_enable: bool,
+}
+
+impl Default for ChargerConfig3 {
+    fn default() -> Self {
+        Self {
+            ac_current_sink_enable: true,
+            dppm_threshold: DppmThreshold::V4_50,
+            precharge_time: PrechargeTime::V30Min,
+            termination_current: TerminationCurrent::V10Percent,
+            usb_current_sink_enable: true,
+        }
+    }
+}
+
+impl From<ChargerConfig3> for CHGCONFIG3 {
+    fn from(v: ChargerConfig3) -> Self {
+        let mut reg = CHGCONFIG3(0x00);
+        reg.set_ac_current_sink_disable(!v.ac_current_sink_enable);
+        reg.set_dppm_threshold(v.dppm_threshold);
+        reg.set_precharge_time(v.precharge_time);
+        reg.set_termination_current(v.termination_current);
+        reg.set_usb_current_sink_disable(!v.usb_current_sink_enable);
+
+        reg
+    }
+}
+
+impl From<CHGCONFIG3> for ChargerConfig3 {
+    fn from(reg: CHGCONFIG3) -> Self {
+        Self {
+            ac_current_sink_enable: !reg.ac_current_sink_disable(),
+            dppm_threshold: reg.dppm_threshold(),
+            precharge_time: reg.precharge_time(),
+            termination_current: reg.termination_current(),
+            usb_current_sink_enable: !reg.usb_current_sink_disable(),
+        }
+    }
+}
+
+/// Power path DPPM threshold
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum DppmThreshold {
+    V3_50 = 0b00,
+    V3_75 = 0b01,
+    V4_25 = 0b10,
+    V4_50 = 0b11,
+}
+
+impl From<u8> for DppmThreshold {
+    fn from(v: u8) -> Self {
+        use DppmThreshold::*;
+        match v {
+            0b00 => V3_50,
+            0b01 => V3_75,
+            0b10 => V4_25,
+            0b11 => V4_50,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<DppmThreshold> for u8 {
+    fn from(v: DppmThreshold) -> Self {
+        v as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum PrechargeTime {
+    V30Min = 0b0,
+    V60Min = 0b1,
+}
+
+impl From<u8> for PrechargeTime {
+    fn from(v: u8) -> Self {
+        use PrechargeTime::*;
+        match v {
+            0b0 => V30Min,
+            0b1 => V60Min,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<PrechargeTime> for u8 {
+    fn from(v: PrechargeTime) -> Self {
+        v as u8
+    }
+}
+
+/// Termination current as a fraction of the fast charge current
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum TerminationCurrent {
+    V4Percent = 0b00,
+    V10Percent = 0b01,
+    V15Percent = 0b10,
+    V20Percent = 0b11,
+}
+
+impl From<u8> for TerminationCurrent {
+    fn from(v: u8) -> Self {
+        use TerminationCurrent::*;
+        match v {
+            0b00 => V4Percent,
+            0b01 => V10Percent,
+            0b10 => V15Percent,
+            0b11 => V20Percent,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<TerminationCurrent> for u8 {
+    fn from(v: TerminationCurrent) -> Self {
+        v as u8
+    }
+}
+
+/// Charger state summarized from the status bits
+///
+/// Note: the device does not report precharge and fast charge phases separately, both are
+/// reported as `Charging`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChargeState {
+    /// Neither AC nor USB power is present
+    NoSource,
+    /// Power is present but the charger is not charging
+    Idle,
+    /// Battery is being charged
+    Charging,
+    /// Charging is suspended because battery temperature is out of range
+    Suspended,
+    /// Termination current threshold has been crossed and charging has been stopped
+    Done,
+    /// Safety timer timed out or no temperature sensor detected
+    Fault,
+}
+
+/// Battery charger status
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChargerStatus {
+    /// USB source is present and valid for charging
+    pub usb_power: bool,
+    /// Wall plug source is present and valid for charging
+    pub ac_power: bool,
+    /// Charger is charging
+    pub charger_active: bool,
+    /// Charge current is reduced due to high chip temperature
+    pub thermal_regulation: bool,
+    /// Charge current is reduced to support the load
+    pub dppm_active: bool,
+    /// Charging is suspended because battery temperature is out of range
+    pub thermal_suspend: bool,
+    /// Charge termination current threshold has been crossed
+    pub termination_current: bool,
+    /// Fast charge safety timer timed out
+    pub charge_timeout: bool,
+    /// Precharge safety timer timed out
+    pub precharge_timeout: bool,
+    /// No battery temperature sensor detected
+    pub battery_temperature_error: bool,
+}
+
+impl ChargerStatus {
+    pub(crate) fn from_regs(
+        ppath1: PPATH1,
+        chgconfig0: CHGCONFIG0,
+        chgconfig3: CHGCONFIG3,
+    ) -> Self {
+        Self {
+            usb_power: ppath1.usb_power(),
+            ac_power: ppath1.ac_power(),
+            charger_active: chgconfig3.charger_active(),
+            thermal_regulation: chgconfig0.thermal_regulation(),
+            dppm_active: chgconfig0.dppm_active(),
+            thermal_suspend: chgconfig0.thermal_suspend(),
+            termination_current: chgconfig0.termination_current(),
+            charge_timeout: chgconfig0.charge_timeout(),
+            precharge_timeout: chgconfig0.precharge_timeout(),
+            battery_temperature_error: chgconfig0.battery_temperature_error(),
+        }
+    }
+
+    /// Returns `true` if either AC or USB power is present
+    pub fn power_present(&self) -> bool {
+        self.usb_power || self.ac_power
+    }
+
+    /// Summarizes status bits into a single charger state
+    pub fn state(&self) -> ChargeState {
+        if !self.power_present() {
+            ChargeState::NoSource
+        } else if self.charge_timeout || self.precharge_timeout || self.battery_temperature_error {
+            ChargeState::Fault
+        } else if self.termination_current {
+            ChargeState::Done
+        } else if self.thermal_suspend {
+            ChargeState::Suspended
+        } else if self.charger_active {
+            ChargeState::Charging
+        } else {
+            ChargeState::Idle
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum SensorType {
     V100K = 0b0,
@@ -57,23 +380,33 @@ impl From<SensorType> for bool {
     }
 }
 
-#[derive(Debug)]
+impl From<bool> for SensorType {
+    fn from(v: bool) -> Self {
+        use SensorType::*;
+        match v {
+            false => V100K,
+            true => V10K,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum SafetyTimerTimeOut {
-    V4Hours = 0x00,
-    V5Hours = 0x01,
-    V6Hours = 0x10,
-    V8Hours = 0x11,
+    V4Hours = 0b00,
+    V5Hours = 0b01,
+    V6Hours = 0b10,
+    V8Hours = 0b11,
 }
 
 impl From<u8> for SafetyTimerTimeOut {
     fn from(v: u8) -> Self {
         use SafetyTimerTimeOut::*;
         match v {
-            0x00 => V4Hours,
-            0x01 => V5Hours,
-            0x10 => V6Hours,
-            0x11 => V8Hours,
+            0b00 => V4Hours,
+            0b01 => V5Hours,
+            0b10 => V6Hours,
+            0b11 => V8Hours,
             _ => unreachable!(),
         }
     }
@@ -85,6 +418,20 @@ impl From<SafetyTimerTimeOut> for u8 {
     }
 }
 
+bitfield! {
+    // 0x03
+    pub struct CHGCONFIG0(u8);
+    impl Debug;
+
+    pub battery_temperature_error, _: 0;
+    pub precharge_timeout, _: 1;
+    pub charge_timeout, _: 2;
+    pub termination_current, _: 4;
+    pub thermal_suspend, _: 5;
+    pub dppm_active, _: 6;
+    pub thermal_regulation, _: 7;
+}
+
 bitfield! {
     // 0x04
     pub struct CHGCONFIG1(u8);
@@ -99,19 +446,25 @@ bitfield! {
     pub from into SafetyTimerTimeOut, charge_safety_timer, set_charge_safety_timer: 7, 6;
 }
 
-#[repr(u8)]
-pub enum BatterySensorType {
-    V100K = 0x00,
-    V10K = 0x01,
+bitfield! {
+    // 0x05
+    pub struct CHGCONFIG2(u8);
+    impl Debug;
+
+    pub from into ChargeVoltage, charge_voltage, set_charge_voltage: 5, 4;
+    pub from into PrechargeVoltage, precharge_voltage, set_precharge_voltage: 6, 6;
+    pub dynamic_timer, set_dynamic_timer: 7;
 }
 
-impl From<u8> for BatterySensorType {
-    fn from(v: u8) -> Self {
-        use BatterySensorType::*;
-        match v {
-            0x00 => V100K,
-            0x01 => V10K,
-            _ => unreachable!(),
-        }
-    }
+bitfield! {
+    // 0x06
+    pub struct CHGCONFIG3(u8);
+    impl Debug;
+
+    pub usb_current_sink_disable, set_usb_current_sink_disable: 0;
+    pub charger_active, _: 1;
+    pub from into TerminationCurrent, termination_current, set_termination_current: 3, 2;
+    pub from into PrechargeTime, precharge_time, set_precharge_time: 4, 4;
+    pub from into DppmThreshold, dppm_threshold, set_dppm_threshold: 6, 5;
+    pub ac_current_sink_disable, set_ac_current_sink_disable: 7;
 }
@@ -1 +1,286 @@
+use super::*;
 
+/// Output rail of the device
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rail {
+    Dcdc1,
+    Dcdc2,
+    Dcdc3,
+    Ldo1,
+    Ldo2,
+}
+
+impl Rail {
+    /// Bit position of the rail in CON_CTRL1, PGOOD and PGOODMASK registers
+    pub(crate) fn bit(self) -> u8 {
+        use Rail::*;
+        match self {
+            Ldo2 => 0,
+            Ldo1 => 1,
+            Dcdc3 => 2,
+            Dcdc2 => 3,
+            Dcdc1 => 4,
+        }
+    }
+}
+
+/// Set of output rails
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RailSet {
+    pub dcdc1: bool,
+    pub dcdc2: bool,
+    pub dcdc3: bool,
+    pub ldo1: bool,
+    pub ldo2: bool,
+}
+
+impl RailSet {
+    /// Set of all rails
+    pub fn all() -> Self {
+        Self {
+            dcdc1: true,
+            dcdc2: true,
+            dcdc3: true,
+            ldo1: true,
+            ldo2: true,
+        }
+    }
+
+    /// Returns `true` if the rail is a member of the set
+    pub fn contains(&self, rail: Rail) -> bool {
+        self.bits() & (1 << rail.bit()) != 0
+    }
+
+    /// Adds or removes the rail from the set
+    pub fn set(&mut self, rail: Rail, member: bool) {
+        use Rail::*;
+        match rail {
+            Dcdc1 => self.dcdc1 = member,
+            Dcdc2 => self.dcdc2 = member,
+            Dcdc3 => self.dcdc3 = member,
+            Ldo1 => self.ldo1 = member,
+            Ldo2 => self.ldo2 = member,
+        }
+    }
+
+    /// Rails encoded as bits 4..0 of CON_CTRL1, PGOOD and PGOODMASK registers
+    pub(crate) fn bits(&self) -> u8 {
+        (self.dcdc1 as u8) << 4
+            | (self.dcdc2 as u8) << 3
+            | (self.dcdc3 as u8) << 2
+            | (self.ldo1 as u8) << 1
+            | self.ldo2 as u8
+    }
+
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        Self {
+            dcdc1: bits & (1 << 4) != 0,
+            dcdc2: bits & (1 << 3) != 0,
+            dcdc3: bits & (1 << 2) != 0,
+            ldo1: bits & (1 << 1) != 0,
+            ldo2: bits & 1 != 0,
+        }
+    }
+}
+
+impl From<Rail> for RailSet {
+    fn from(rail: Rail) -> Self {
+        Self::from_bits(1 << rail.bit())
+    }
+}
+
+/// Power-up sequencing of the device, power-down sequencing is the reverse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SequenceMode {
+    pub dcdc: DcdcSequence,
+    pub ldo: ldo::LdoSequence,
+}
+
+/// DCDC converters power-up sequencing
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum DcdcSequence {
+    /// DCDC2 only, DCDC1 and DCDC3 are enabled by their enable pins
+    Dcdc2 = 0b000,
+    /// DCDC2 and DCDC3 at the same time, DCDC1 is enabled by its enable pin
+    Dcdc2Dcdc3 = 0b001,
+    /// DCDC1, when power good then DCDC2 and DCDC3 at the same time
+    Dcdc1ThenDcdc2Dcdc3 = 0b010,
+    /// DCDC3, when power good then DCDC2, DCDC1 is enabled by its enable pin
+    Dcdc3ThenDcdc2 = 0b011,
+    /// DCDC3 with LDO2 (if MASK_EN_DCDC3 is set), DCDC1 and DCDC2 when LDO2 is power good
+    /// (SiRF Prima)
+    Prima = 0b100,
+    /// Converters are enabled individually by their enable pins
+    EnablePins = 0b101,
+    /// DCDC1, when power good then DCDC2, when power good then DCDC3
+    Dcdc1ThenDcdc2ThenDcdc3 = 0b110,
+    /// DCDC1 and DCDC2 at the same time >1 ms after LDO2, DCDC3 is enabled by its enable pin
+    /// (SiRF Atlas4)
+    Atlas4 = 0b111,
+}
+
+impl From<u8> for DcdcSequence {
+    fn from(v: u8) -> Self {
+        use DcdcSequence::*;
+        match v {
+            0b000 => Dcdc2,
+            0b001 => Dcdc2Dcdc3,
+            0b010 => Dcdc1ThenDcdc2Dcdc3,
+            0b011 => Dcdc3ThenDcdc2,
+            0b100 => Prima,
+            0b101 => EnablePins,
+            0b110 => Dcdc1ThenDcdc2ThenDcdc3,
+            0b111 => Atlas4,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<DcdcSequence> for u8 {
+    fn from(v: DcdcSequence) -> Self {
+        v as u8
+    }
+}
+
+bitfield! {
+    // 0x0D
+    pub struct CON_CTRL1(u8);
+    impl Debug;
+
+    pub rails, set_rails: 4, 0;
+    pub from into DcdcSequence, dcdc_seq, set_dcdc_seq: 7, 5;
+}
+
+#[derive(Debug)]
+pub struct ControlConfig {
+    pub timer_1s_enable: bool,
+    pub timer_5s_enable: bool,
+    pub deep_sleep_ready: bool,
+    pub deep_sleep: bool,
+    pub mask_en_dcdc3: bool,
+    pub uvlo_hysteresis: UvloHysteresis,
+    pub uvlo_threshold: UvloThreshold,
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            timer_1s_enable: false,
+            timer_5s_enable: false,
+            deep_sleep_ready: false,
+            deep_sleep: false,
+            mask_en_dcdc3: true,
+            uvlo_hysteresis: UvloHysteresis::V500mV,
+            uvlo_threshold: UvloThreshold::V3_00,
+        }
+    }
+}
+
+impl From<ControlConfig> for CON_CTRL2 {
+    fn from(v: ControlConfig) -> Self {
+        let mut reg = CON_CTRL2(0x00);
+        reg.set_timer_1s_enable(v.timer_1s_enable);
+        reg.set_timer_5s_enable(v.timer_5s_enable);
+        reg.set_ds_rdy(v.deep_sleep_ready);
+        reg.set_pwr_ds(v.deep_sleep);
+        reg.set_mask_en_dcdc3(v.mask_en_dcdc3);
+        reg.set_uvlo_hysteresis(v.uvlo_hysteresis);
+        reg.set_uvlo(v.uvlo_threshold);
+
+        reg
+    }
+}
+
+impl From<CON_CTRL2> for ControlConfig {
+    fn from(reg: CON_CTRL2) -> Self {
+        Self {
+            timer_1s_enable: reg.timer_1s_enable(),
+            timer_5s_enable: reg.timer_5s_enable(),
+            deep_sleep_ready: reg.ds_rdy(),
+            deep_sleep: reg.pwr_ds(),
+            mask_en_dcdc3: reg.mask_en_dcdc3(),
+            uvlo_hysteresis: reg.uvlo_hysteresis(),
+            uvlo_threshold: reg.uvlo(),
+        }
+    }
+}
+
+/// Undervoltage lockout hysteresis
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum UvloHysteresis {
+    V400mV = 0b0,
+    V500mV = 0b1,
+}
+
+impl From<u8> for UvloHysteresis {
+    fn from(v: u8) -> Self {
+        use UvloHysteresis::*;
+        match v {
+            0b0 => V400mV,
+            0b1 => V500mV,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<UvloHysteresis> for u8 {
+    fn from(v: UvloHysteresis) -> Self {
+        v as u8
+    }
+}
+
+/// Undervoltage lockout voltage sensed at the SYS pin
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum UvloThreshold {
+    V2_80 = 0b00,
+    V3_00 = 0b01,
+    V3_10 = 0b10,
+    V3_25 = 0b11,
+}
+
+impl From<u8> for UvloThreshold {
+    fn from(v: u8) -> Self {
+        use UvloThreshold::*;
+        match v {
+            0b00 => V2_80,
+            0b01 => V3_00,
+            0b10 => V3_10,
+            0b11 => V3_25,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<UvloThreshold> for u8 {
+    fn from(v: UvloThreshold) -> Self {
+        v as u8
+    }
+}
+
+bitfield! {
+    // 0x0E
+    pub struct CON_CTRL2(u8);
+    impl Debug;
+
+    pub from into UvloThreshold, uvlo, set_uvlo: 1, 0;
+    pub from into UvloHysteresis, uvlo_hysteresis, set_uvlo_hysteresis: 2, 2;
+    pub mask_en_dcdc3, set_mask_en_dcdc3: 3;
+    pub pwr_ds, set_pwr_ds: 4;
+    pub ds_rdy, set_ds_rdy: 5;
+    pub timer_5s_enable, set_timer_5s_enable: 6;
+    pub timer_1s_enable, set_timer_1s_enable: 7;
+}
+
+bitfield! {
+    // 0x0F
+    pub struct CON_CTRL3(u8);
+    impl Debug;
+
+    pub discharge, set_discharge: 4, 0;
+    pub fpwm_dcdc1, set_fpwm_dcdc1: 5;
+    pub fpwm_dcdc2, set_fpwm_dcdc2: 6;
+    pub fpwm_dcdc3, set_fpwm_dcdc3: 7;
+}
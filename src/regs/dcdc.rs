@@ -1,5 +1,105 @@
 use super::*;
 
+/// Step-down converter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dcdc {
+    Dcdc1,
+    Dcdc2,
+    Dcdc3,
+}
+
+/// Source of the converter output voltage setpoint
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VoltageAdjust {
+    /// Output voltage is defined by the DEFDCDCx registers
+    Internal,
+    /// Output voltage is defined by an external resistor divider on the VDCDCx pin
+    External,
+}
+
+impl From<bool> for VoltageAdjust {
+    fn from(v: bool) -> Self {
+        use VoltageAdjust::*;
+        match v {
+            false => Internal,
+            true => External,
+        }
+    }
+}
+
+impl From<VoltageAdjust> for bool {
+    fn from(v: VoltageAdjust) -> Self {
+        use VoltageAdjust::*;
+        match v {
+            Internal => false,
+            External => true,
+        }
+    }
+}
+
+/// Slew rate of the DCDC2 and DCDC3 output voltage change
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum SlewRate {
+    V0_11mVus = 0b000,
+    V0_22mVus = 0b001,
+    V0_45mVus = 0b010,
+    V0_9mVus = 0b011,
+    V1_8mVus = 0b100,
+    V3_6mVus = 0b101,
+    V7_2mVus = 0b110,
+    Immediate = 0b111,
+}
+
+impl SlewRate {
+    /// Slew rate in microvolts per microsecond, `None` if voltage is changed immediately
+    pub fn microvolts_per_us(self) -> Option<u32> {
+        use SlewRate::*;
+        match self {
+            V0_11mVus => Some(110),
+            V0_22mVus => Some(220),
+            V0_45mVus => Some(450),
+            V0_9mVus => Some(900),
+            V1_8mVus => Some(1800),
+            V3_6mVus => Some(3600),
+            V7_2mVus => Some(7200),
+            Immediate => None,
+        }
+    }
+
+    /// Expected time in microseconds for the output to slew from `current` to `target` voltage
+    pub fn settling_time_us(self, current: DCDCVoltage, target: DCDCVoltage) -> u32 {
+        let delta = (current.millivolts() as i32 - target.millivolts() as i32).unsigned_abs();
+        match self.microvolts_per_us() {
+            Some(rate) => (delta * 1000).div_ceil(rate),
+            None => 0,
+        }
+    }
+}
+
+impl From<u8> for SlewRate {
+    fn from(v: u8) -> Self {
+        use SlewRate::*;
+        match v {
+            0b000 => V0_11mVus,
+            0b001 => V0_22mVus,
+            0b010 => V0_45mVus,
+            0b011 => V0_9mVus,
+            0b100 => V1_8mVus,
+            0b101 => V3_6mVus,
+            0b110 => V7_2mVus,
+            0b111 => Immediate,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<SlewRate> for u8 {
+    fn from(v: SlewRate) -> Self {
+        v as u8
+    }
+}
+
 bitfield! {
     // 0x10
     pub struct DEFDCDC1(u8);
@@ -9,6 +109,14 @@ bitfield! {
     pub extadj, set_extadj: 7;
 }
 
+bitfield! {
+    // 0x11
+    pub struct DEFDCDC2_LOW(u8);
+    impl Debug;
+
+    pub from into DCDCVoltage, dcdc2, set_dcdc2: 5, 0;
+}
+
 bitfield! {
     // 0x12
     pub struct DEFDCDC2_HIGH(u8);
@@ -18,6 +126,14 @@ bitfield! {
     pub extadj, set_extadj: 7;
 }
 
+bitfield! {
+    // 0x13
+    pub struct DEFDCDC3_LOW(u8);
+    impl Debug;
+
+    pub from into DCDCVoltage, dcdc3, set_dcdc3: 5, 0;
+}
+
 bitfield! {
     // 0x14
     pub struct DEFDCDC3_HIGH(u8);
@@ -26,3 +142,11 @@ bitfield! {
     pub from into DCDCVoltage, dcdc3, set_dcdc3: 5, 0;
     pub extadj, set_extadj: 7;
 }
+
+bitfield! {
+    // 0x15
+    pub struct DEFSLEW(u8);
+    impl Debug;
+
+    pub from into SlewRate, slew, set_slew: 2, 0;
+}
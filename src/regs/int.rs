@@ -1 +1,78 @@
+use super::*;
 
+/// Sources which are allowed to pull the INT pin low
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterruptMask {
+    /// Voltage at AC or USB applied or removed
+    pub power_source: bool,
+    /// Touch detected by the touch screen controller
+    pub touch_screen: bool,
+    /// PB_IN pulled low
+    pub push_button: bool,
+}
+
+impl From<InterruptMask> for INT {
+    fn from(v: InterruptMask) -> Self {
+        let mut reg = INT(0x00);
+        reg.set_mask_ac_usb(v.power_source);
+        reg.set_mask_tsc(v.touch_screen);
+        reg.set_mask_pb_in(v.push_button);
+
+        reg
+    }
+}
+
+impl From<INT> for InterruptMask {
+    fn from(reg: INT) -> Self {
+        Self {
+            power_source: reg.mask_ac_usb(),
+            touch_screen: reg.mask_tsc(),
+            push_button: reg.mask_pb_in(),
+        }
+    }
+}
+
+/// Interrupt events latched by the device since the INT register was read last time
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InterruptEvents {
+    /// Voltage at USB or AC has been applied
+    pub power_applied: bool,
+    /// Voltage at USB or AC has been removed
+    pub power_removed: bool,
+    /// PB_IN has been pulled low
+    pub push_button: bool,
+    /// Touch screen "touch" has been detected
+    pub touch_screen: bool,
+}
+
+impl InterruptEvents {
+    /// Returns `true` if no event has been latched
+    pub fn is_empty(&self) -> bool {
+        !(self.power_applied || self.power_removed || self.push_button || self.touch_screen)
+    }
+}
+
+impl From<INT> for InterruptEvents {
+    fn from(reg: INT) -> Self {
+        Self {
+            power_applied: reg.voltage_applied(),
+            power_removed: reg.voltage_removed(),
+            push_button: reg.pb_in_int(),
+            touch_screen: reg.tsc_int(),
+        }
+    }
+}
+
+bitfield! {
+    // 0x02
+    pub struct INT(u8);
+    impl Debug;
+
+    pub voltage_removed, _: 0;
+    pub voltage_applied, _: 1;
+    pub pb_in_int, _: 2;
+    pub tsc_int, _: 3;
+    pub mask_pb_in, set_mask_pb_in: 5;
+    pub mask_tsc, set_mask_tsc: 6;
+    pub mask_ac_usb, set_mask_ac_usb: 7;
+}
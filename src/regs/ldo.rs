@@ -1,12 +1,58 @@
 use super::*;
 
+/// LDO power-up sequencing
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum LdoSequence {
+    /// LDO1 and LDO2 are enabled as soon as the device is in ON-state
+    OnState = 0b000,
+    /// LDO1 and LDO2 are enabled after DCDC3 is power good
+    AfterDcdc3 = 0b001,
+    /// EN_EXTLDO first, LDO2 >1 ms later, LDO1 with DCDC3 (SiRF Atlas4)
+    Atlas4 = 0b010,
+    /// LDO1 300 us after DCDC1 is power good, LDO2 is off
+    Ldo1AfterDcdc1 = 0b011,
+    /// LDO1 after DCDC1 is power good, LDO2 with DCDC3
+    Ldo1AfterDcdc1Ldo2WithDcdc3 = 0b100,
+    /// LDO1 with DCDC2, LDO2 after DCDC1 is power good
+    Ldo1WithDcdc2Ldo2AfterDcdc1 = 0b101,
+    /// LDO1 10 ms after DCDC2 is power good, LDO2 is off
+    Ldo1AfterDcdc2 = 0b110,
+    /// EN_EXTLDO first, LDO2 >1 ms later, LDO1 when EN_DCDC3 is HIGH and DCDC3 is power good
+    /// (SiRF Prima)
+    Prima = 0b111,
+}
+
+impl From<u8> for LdoSequence {
+    fn from(v: u8) -> Self {
+        use LdoSequence::*;
+        match v {
+            0b000 => OnState,
+            0b001 => AfterDcdc3,
+            0b010 => Atlas4,
+            0b011 => Ldo1AfterDcdc1,
+            0b100 => Ldo1AfterDcdc1Ldo2WithDcdc3,
+            0b101 => Ldo1WithDcdc2Ldo2AfterDcdc1,
+            0b110 => Ldo1AfterDcdc2,
+            0b111 => Prima,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<LdoSequence> for u8 {
+    fn from(v: LdoSequence) -> Self {
+        v as u8
+    }
+}
+
 bitfield! {
     // 0x16
     pub struct LDO_CTRL1(u8);
     impl Debug;
 
     pub from into LDO1Voltage, ldo1, set_ldo1: 3, 0;
-    pub ldo_seq, set_ldo_seq: 7, 5;
+    pub from into LdoSequence, ldo_seq, set_ldo_seq: 7, 5;
 }
 
 bitfield! {
@@ -1 +1,95 @@
+use super::*;
+use crate::regs::ctrl::RailSet;
 
+/// Power good status of the device
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerGood {
+    /// Rails with output voltage within the nominal range
+    pub rails: RailSet,
+    /// Voltage at the THRESHOLD comparator input is below 1 V, RESET output is asserted
+    pub threshold_low: bool,
+}
+
+impl From<PGOOD> for PowerGood {
+    fn from(reg: PGOOD) -> Self {
+        Self {
+            rails: RailSet::from_bits(reg.rails()),
+            threshold_low: reg.reset(),
+        }
+    }
+}
+
+/// Rails which drive the PGOOD output low when their output voltage is not good
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PowerGoodMask {
+    pub rails: RailSet,
+    /// PGOOD output is driven low only if both DCDC3 and LDO1 are not good
+    pub dcdc3_and_ldo1: bool,
+}
+
+impl From<PowerGoodMask> for PGOODMASK {
+    fn from(v: PowerGoodMask) -> Self {
+        let mut reg = PGOODMASK(0x00);
+        reg.set_rails(v.rails.bits());
+        reg.set_dcdc3_and_ldo1(v.dcdc3_and_ldo1);
+
+        reg
+    }
+}
+
+impl From<PGOODMASK> for PowerGoodMask {
+    fn from(reg: PGOODMASK) -> Self {
+        Self {
+            rails: RailSet::from_bits(reg.rails()),
+            dcdc3_and_ldo1: reg.dcdc3_and_ldo1(),
+        }
+    }
+}
+
+/// Delay time of the reset and PGOOD outputs
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum PowerGoodDelay {
+    V20ms = 0b00,
+    V100ms = 0b01,
+    V200ms = 0b10,
+    V400ms = 0b11,
+}
+
+impl From<u8> for PowerGoodDelay {
+    fn from(v: u8) -> Self {
+        use PowerGoodDelay::*;
+        match v {
+            0b00 => V20ms,
+            0b01 => V100ms,
+            0b10 => V200ms,
+            0b11 => V400ms,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<PowerGoodDelay> for u8 {
+    fn from(v: PowerGoodDelay) -> Self {
+        v as u8
+    }
+}
+
+bitfield! {
+    // 0x0B
+    pub struct PGOOD(u8);
+    impl Debug;
+
+    pub rails, _: 4, 0;
+    pub from into PowerGoodDelay, delay, set_delay: 6, 5;
+    pub reset, _: 7;
+}
+
+bitfield! {
+    // 0x0C
+    pub struct PGOODMASK(u8);
+    impl Debug;
+
+    pub rails, set_rails: 4, 0;
+    pub dcdc3_and_ldo1, set_dcdc3_and_ldo1: 5;
+}
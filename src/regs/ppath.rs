@@ -11,14 +11,25 @@ pub struct PowerPath {
 impl From<PowerPath> for PPATH1 {
     fn from(power_path: PowerPath) -> Self {
         let mut reg = PPATH1(0x00);
-        reg.set_usb_input_current(power_path.usb_input_current as u8);
-        reg.set_ac_input_current(power_path.ac_input_current as u8);
+        reg.set_usb_input_current(power_path.usb_input_current);
+        reg.set_ac_input_current(power_path.ac_input_current);
         reg.set_ac_power_disable(!power_path.ac_power_enable);
         reg.set_usb_power_disable(!power_path.usb_power_enable);
         reg
     }
 }
 
+impl From<PPATH1> for PowerPath {
+    fn from(reg: PPATH1) -> Self {
+        PowerPath {
+            usb_power_enable: !reg.usb_power_disable(),
+            usb_input_current: reg.usb_input_current(),
+            ac_power_enable: !reg.ac_disable(),
+            ac_input_current: reg.ac_input_current(),
+        }
+    }
+}
+
 impl Default for PowerPath {
     fn default() -> Self {
         PowerPath {
@@ -30,7 +41,7 @@ impl Default for PowerPath {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum AcInputCurrent {
     V100mA = 0b00,
@@ -39,7 +50,26 @@ pub enum AcInputCurrent {
     V2500mA = 0b11,
 }
 
-#[derive(Debug)]
+impl From<u8> for AcInputCurrent {
+    fn from(v: u8) -> Self {
+        use AcInputCurrent::*;
+        match v {
+            0b00 => V100mA,
+            0b01 => V500mA,
+            0b10 => V1300mA,
+            0b11 => V2500mA,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<AcInputCurrent> for u8 {
+    fn from(v: AcInputCurrent) -> Self {
+        v as u8
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(u8)]
 pub enum UsbInputCurrent {
     V100mA = 0b00,
@@ -48,13 +78,32 @@ pub enum UsbInputCurrent {
     V1300mA = 0b11,
 }
 
+impl From<u8> for UsbInputCurrent {
+    fn from(v: u8) -> Self {
+        use UsbInputCurrent::*;
+        match v {
+            0b00 => V100mA,
+            0b01 => V500mA,
+            0b10 => V800mA,
+            0b11 => V1300mA,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<UsbInputCurrent> for u8 {
+    fn from(v: UsbInputCurrent) -> Self {
+        v as u8
+    }
+}
+
 bitfield! {
     // 0x01
     pub struct PPATH1(u8);
     impl Debug;
 
-    pub usb_input_current, set_usb_input_current: 1, 0;
-    pub ac_input_current, set_ac_input_current: 3, 2;
+    pub from into UsbInputCurrent, usb_input_current, set_usb_input_current: 1, 0;
+    pub from into AcInputCurrent, ac_input_current, set_ac_input_current: 3, 2;
     pub ac_disable, set_ac_power_disable: 4;
     pub usb_power_disable, set_usb_power_disable: 5;
     pub ac_power, _: 6;
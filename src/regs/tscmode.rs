@@ -1 +1,65 @@
+use super::*;
 
+/// Touch screen controller state, selects the switch matrix connection to the panel
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum TouchScreenMode {
+    /// X-position measurement, voltage at TSY1
+    XPosition = 0b000,
+    /// Y-position measurement, voltage at TSX1
+    YPosition = 0b001,
+    /// Pressure measurement, current through TSX1 and TSX2
+    Pressure = 0b010,
+    /// X plate resistance measurement, current through TSX1
+    PlateX = 0b011,
+    /// Y plate resistance measurement, current through TSY1
+    PlateY = 0b100,
+    /// Low power standby, a touch sets TSC INT in the INT register
+    Standby = 0b101,
+    /// AD_IN1..AD_IN4 are used as plain ADC inputs
+    Adc = 0b110,
+    /// Panel disconnected, touches are not detected
+    Disabled = 0b111,
+}
+
+impl From<u8> for TouchScreenMode {
+    fn from(v: u8) -> Self {
+        use TouchScreenMode::*;
+        match v {
+            0b000 => XPosition,
+            0b001 => YPosition,
+            0b010 => Pressure,
+            0b011 => PlateX,
+            0b100 => PlateY,
+            0b101 => Standby,
+            0b110 => Adc,
+            0b111 => Disabled,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<TouchScreenMode> for u8 {
+    fn from(v: TouchScreenMode) -> Self {
+        v as u8
+    }
+}
+
+/// Raw 10 bit touch screen conversion results
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TouchSample {
+    /// X-position, `x / 1024` is the relative position on the X plate
+    pub x: u16,
+    /// Y-position, `y / 1024` is the relative position on the Y plate
+    pub y: u16,
+    /// Pressure measurement, rises with the contact pressure
+    pub pressure: u16,
+}
+
+bitfield! {
+    // 0x08
+    pub struct TSCMODE(u8);
+    impl Debug;
+
+    pub from into TouchScreenMode, mode, set_mode: 2, 0;
+}
@@ -1 +1,96 @@
+use super::*;
 
+/// PWM dimming frequency of the wLED current sinks
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum DimmingFrequency {
+    V100Hz = 0b00,
+    V200Hz = 0b01,
+    V500Hz = 0b10,
+    V1000Hz = 0b11,
+}
+
+impl From<u8> for DimmingFrequency {
+    fn from(v: u8) -> Self {
+        use DimmingFrequency::*;
+        match v {
+            0b00 => V100Hz,
+            0b01 => V200Hz,
+            0b10 => V500Hz,
+            0b11 => V1000Hz,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<DimmingFrequency> for u8 {
+    fn from(v: DimmingFrequency) -> Self {
+        v as u8
+    }
+}
+
+/// Resistor defining the wLED current sinks current
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum CurrentLevel {
+    /// Current defined with resistor connected from ISET2 to GND
+    Iset2 = 0b0,
+    /// Current defined with resistor connected from ISET1 to GND
+    Iset1 = 0b1,
+}
+
+impl From<u8> for CurrentLevel {
+    fn from(v: u8) -> Self {
+        use CurrentLevel::*;
+        match v {
+            0b0 => Iset2,
+            0b1 => Iset1,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<CurrentLevel> for u8 {
+    fn from(v: CurrentLevel) -> Self {
+        v as u8
+    }
+}
+
+/// Duty cycle code for 0 %, any value above 100 % code turns the sinks off
+const DUTY_OFF: u8 = 0b111_1111;
+/// Duty cycle code for 100 %
+const DUTY_MAX: u8 = 0b110_0011;
+
+/// Converts brightness in percent, clamped to 100, into duty cycle code
+pub(crate) fn duty_from_percent(percent: u8) -> u8 {
+    match percent {
+        0 => DUTY_OFF,
+        p => p.min(100) - 1,
+    }
+}
+
+/// Converts duty cycle code into brightness in percent
+pub(crate) fn percent_from_duty(duty: u8) -> u8 {
+    match duty {
+        d if d > DUTY_MAX => 0,
+        d => d + 1,
+    }
+}
+
+bitfield! {
+    // 0x18
+    pub struct WLED_CTRL1(u8);
+    impl Debug;
+
+    pub from into DimmingFrequency, dimming_frequency, set_dimming_frequency: 5, 4;
+    pub isink_enable, set_isink_enable: 7;
+}
+
+bitfield! {
+    // 0x19
+    pub struct WLED_CTRL2(u8);
+    impl Debug;
+
+    pub duty, set_duty: 6, 0;
+    pub from into CurrentLevel, current_level, set_current_level: 7, 7;
+}
@@ -0,0 +1,160 @@
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::i2c;
+
+use crate::device::{Tps6507x, Tps6507xError, Tps6507xResult};
+use crate::regs::adc::AdcInputSelect;
+
+/// ADC input converted by a scan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanChannel {
+    pub input: AdcInputSelect,
+    /// Keep reference voltage LDO (pin BYPASS) enabled
+    pub vref: bool,
+}
+
+impl ScanChannel {
+    pub fn new(input: AdcInputSelect, vref: bool) -> Self {
+        Self { input, vref }
+    }
+}
+
+impl From<AdcInputSelect> for ScanChannel {
+    fn from(input: AdcInputSelect) -> Self {
+        Self { input, vref: false }
+    }
+}
+
+/// Raw results of all oversampled conversions of a channel
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScanResult {
+    /// Mean, rounded to nearest
+    pub average: u16,
+    pub min: u16,
+    pub max: u16,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Accumulator {
+    sum: u32,
+    count: u32,
+    min: u16,
+    max: u16,
+}
+
+impl Accumulator {
+    const EMPTY: Self = Self {
+        sum: 0,
+        count: 0,
+        min: u16::MAX,
+        max: 0,
+    };
+
+    fn add(&mut self, sample: u16) {
+        self.sum += sample as u32;
+        self.count += 1;
+        self.min = self.min.min(sample);
+        self.max = self.max.max(sample);
+    }
+
+    fn result(&self) -> ScanResult {
+        ScanResult {
+            average: ((self.sum + self.count / 2) / self.count) as u16,
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+/// Sequential conversion of `N` ADC channels, each averaged over several samples
+///
+/// A scan is either run at once with [`Self::run`], or step by step with [`Self::poll`].
+#[derive(Debug, Clone)]
+pub struct AdcScan<const N: usize> {
+    channels: [ScanChannel; N],
+    oversampling: u8,
+    results: [ScanResult; N],
+    channel: usize,
+    accumulator: Accumulator,
+}
+
+impl<const N: usize> AdcScan<N> {
+    /// Creates a scan taking `oversampling` conversions per channel, at least one
+    pub fn new(channels: [ScanChannel; N], oversampling: u8) -> Self {
+        Self {
+            channels,
+            oversampling: oversampling.max(1),
+            results: [ScanResult::default(); N],
+            channel: 0,
+            accumulator: Accumulator::EMPTY,
+        }
+    }
+
+    pub fn channels(&self) -> &[ScanChannel; N] {
+        &self.channels
+    }
+
+    /// Results of the last completed scan, in channel order
+    pub fn results(&self) -> &[ScanResult; N] {
+        &self.results
+    }
+
+    /// Advances the scan, returns `WouldBlock` until all channels are converted
+    ///
+    /// Never waits for the ADC, so it can be called from a main loop or timer. A completed scan
+    /// restarts with the next call.
+    pub fn poll<I2C, E>(
+        &mut self,
+        tps: &mut Tps6507x<I2C>,
+    ) -> nb::Result<&[ScanResult; N], Tps6507xError<E>>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+    {
+        while self.channel < N {
+            let channel = self.channels[self.channel];
+            let sample = tps.read_adc(channel.input, channel.vref)?;
+            self.add(sample);
+        }
+        self.restart();
+        Ok(&self.results)
+    }
+
+    /// Runs a complete scan with blocking conversions, see [`Tps6507x::read_adc_input_blocking`]
+    pub fn run<I2C, E, D>(
+        &mut self,
+        tps: &mut Tps6507x<I2C>,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<&[ScanResult; N], E>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        D: DelayUs<u16>,
+    {
+        self.restart();
+        while self.channel < N {
+            let channel = self.channels[self.channel];
+            match tps.read_adc_input_blocking(channel.input, channel.vref, delay, timeout_us) {
+                Ok(sample) => self.add(sample),
+                Err(e) => {
+                    self.restart();
+                    return Err(e);
+                }
+            }
+        }
+        self.restart();
+        Ok(&self.results)
+    }
+
+    fn restart(&mut self) {
+        self.channel = 0;
+        self.accumulator = Accumulator::EMPTY;
+    }
+
+    fn add(&mut self, sample: u16) {
+        self.accumulator.add(sample);
+        if self.accumulator.count >= self.oversampling as u32 {
+            self.results[self.channel] = self.accumulator.result();
+            self.accumulator = Accumulator::EMPTY;
+            self.channel += 1;
+        }
+    }
+}
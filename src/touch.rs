@@ -0,0 +1,305 @@
+use embedded_hal::blocking::delay::DelayUs;
+use embedded_hal::blocking::i2c;
+
+use crate::device::{Tps6507x, Tps6507xResult};
+
+/// Maximum number of touch screen samples combined into a single point
+pub const MAX_TOUCH_SAMPLES: usize = 16;
+
+/// Touch screen point, raw ADC results or calibrated screen coordinates
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+}
+
+/// Affine transformation from raw touch screen readings to screen coordinates
+///
+/// Compensates offset, scaling, rotation and skew of the panel. Coefficients are calculated
+/// from three reference points, which should be far apart and not on a single line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    a: i64,
+    b: i64,
+    c: i64,
+    d: i64,
+    e: i64,
+    f: i64,
+    divider: i64,
+}
+
+impl Calibration {
+    /// Size of the persisted calibration, seven coefficients and a check byte
+    pub const BYTES: usize = 57;
+
+    /// Calibration passing raw readings through unchanged
+    pub fn identity() -> Self {
+        Self {
+            a: 1,
+            b: 0,
+            c: 0,
+            d: 0,
+            e: 1,
+            f: 0,
+            divider: 1,
+        }
+    }
+
+    /// Calculates calibration from raw readings of three displayed `screen` targets
+    ///
+    /// Returns `None` if the raw points are on a single line.
+    pub fn from_points(raw: [Point; 3], screen: [Point; 3]) -> Option<Self> {
+        let [x0, x1, x2] = raw.map(|p| p.x as i64);
+        let [y0, y1, y2] = raw.map(|p| p.y as i64);
+        let [xd0, xd1, xd2] = screen.map(|p| p.x as i64);
+        let [yd0, yd1, yd2] = screen.map(|p| p.y as i64);
+
+        let divider = (x0 - x2) * (y1 - y2) - (x1 - x2) * (y0 - y2);
+        if divider == 0 {
+            return None;
+        }
+
+        Some(Self {
+            a: (xd0 - xd2) * (y1 - y2) - (xd1 - xd2) * (y0 - y2),
+            b: (x0 - x2) * (xd1 - xd2) - (xd0 - xd2) * (x1 - x2),
+            c: y0 * (x2 * xd1 - x1 * xd2) + y1 * (x0 * xd2 - x2 * xd0) + y2 * (x1 * xd0 - x0 * xd1),
+            d: (yd0 - yd2) * (y1 - y2) - (yd1 - yd2) * (y0 - y2),
+            e: (x0 - x2) * (yd1 - yd2) - (yd0 - yd2) * (x1 - x2),
+            f: y0 * (x2 * yd1 - x1 * yd2) + y1 * (x0 * yd2 - x2 * yd0) + y2 * (x1 * yd0 - x0 * yd1),
+            divider,
+        })
+    }
+
+    /// Maps a raw reading to screen coordinates
+    pub fn apply(&self, raw: Point) -> Point {
+        let (x, y) = (raw.x as i64, raw.y as i64);
+        Point {
+            x: ((self.a * x + self.b * y + self.c) / self.divider) as i32,
+            y: ((self.d * x + self.e * y + self.f) / self.divider) as i32,
+        }
+    }
+
+    /// Serializes coefficients as little endian bytes for non-volatile storage
+    pub fn to_bytes(&self) -> [u8; Self::BYTES] {
+        let coefficients = [self.a, self.b, self.c, self.d, self.e, self.f, self.divider];
+        let mut bytes = [0; Self::BYTES];
+        for (chunk, coefficient) in bytes.chunks_exact_mut(8).zip(coefficients) {
+            chunk.copy_from_slice(&coefficient.to_le_bytes());
+        }
+        bytes[Self::BYTES - 1] = Self::check_byte(&bytes[..Self::BYTES - 1]);
+        bytes
+    }
+
+    /// Restores calibration stored by [`Self::to_bytes`]
+    ///
+    /// Returns `None` for blank or corrupted storage.
+    pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> Option<Self> {
+        if bytes[Self::BYTES - 1] != Self::check_byte(&bytes[..Self::BYTES - 1]) {
+            return None;
+        }
+        let mut coefficients = [0i64; 7];
+        for (coefficient, chunk) in coefficients.iter_mut().zip(bytes.chunks_exact(8)) {
+            let mut le = [0; 8];
+            le.copy_from_slice(chunk);
+            *coefficient = i64::from_le_bytes(le);
+        }
+        let [a, b, c, d, e, f, divider] = coefficients;
+        if divider == 0 {
+            return None;
+        }
+        Some(Self {
+            a,
+            b,
+            c,
+            d,
+            e,
+            f,
+            divider,
+        })
+    }
+
+    /// XOR of `data` seeded with 0xA5, neither erased (0xFF) nor zeroed storage passes the check
+    fn check_byte(data: &[u8]) -> u8 {
+        data.iter().fold(0xA5, |acc, b| acc ^ b)
+    }
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Method combining several ADC samples into one value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Filter {
+    /// Middle value, rejects single outliers
+    Median,
+    /// Arithmetic mean, reduces noise
+    Average,
+}
+
+impl Filter {
+    /// Combines `samples`, which are reordered by the median filter
+    pub fn apply(self, samples: &mut [u16]) -> u16 {
+        if samples.is_empty() {
+            return 0;
+        }
+        match self {
+            Filter::Median => {
+                samples.sort_unstable();
+                samples[samples.len() / 2]
+            }
+            Filter::Average => {
+                let sum: u32 = samples.iter().map(|&s| s as u32).sum();
+                (sum / samples.len() as u32) as u16
+            }
+        }
+    }
+}
+
+/// Touch screen pipeline settings
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchConfig {
+    /// Samples per point, clamped to 1..=[`MAX_TOUCH_SAMPLES`]
+    pub samples: u8,
+    /// Method combining the samples
+    pub filter: Filter,
+    /// Samples with lower pressure reading are rejected as no contact
+    pub pressure_threshold: u16,
+    /// Number of consecutive polls required to report pen down or pen up
+    pub debounce: u8,
+}
+
+impl Default for TouchConfig {
+    fn default() -> Self {
+        Self {
+            samples: 5,
+            filter: Filter::Median,
+            pressure_threshold: 32,
+            debounce: 2,
+        }
+    }
+}
+
+/// Debounced touch screen event in screen coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PenEvent {
+    Down(Point),
+    Move(Point),
+    Up,
+}
+
+/// Calibrated and filtered touch screen input
+#[derive(Debug, Clone)]
+pub struct TouchScreen {
+    config: TouchConfig,
+    calibration: Calibration,
+    pen_down: bool,
+    pending: u8,
+    last: Point,
+}
+
+impl TouchScreen {
+    pub fn new(config: TouchConfig, calibration: Calibration) -> Self {
+        Self {
+            config,
+            calibration,
+            pen_down: false,
+            pending: 0,
+            last: Point::default(),
+        }
+    }
+
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    pub fn calibration(&self) -> Calibration {
+        self.calibration
+    }
+
+    /// Returns `true` if the debounced pen state is down
+    pub fn is_pen_down(&self) -> bool {
+        self.pen_down
+    }
+
+    /// Last reported position in screen coordinates
+    pub fn position(&self) -> Point {
+        self.last
+    }
+
+    /// Takes the configured number of samples and feeds the result to [`Self::update`]
+    ///
+    /// Samples below the pressure threshold are dropped. The point counts as touched if the
+    /// majority of samples is left, the remaining X and Y readings are filtered and calibrated.
+    pub fn poll<I2C, E, D>(
+        &mut self,
+        tps: &mut Tps6507x<I2C>,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Tps6507xResult<Option<PenEvent>, E>
+    where
+        I2C: i2c::WriteRead<Error = E> + i2c::Write<Error = E>,
+        D: DelayUs<u16>,
+    {
+        let samples = (self.config.samples as usize).clamp(1, MAX_TOUCH_SAMPLES);
+        let mut xs = [0u16; MAX_TOUCH_SAMPLES];
+        let mut ys = [0u16; MAX_TOUCH_SAMPLES];
+        let mut valid = 0;
+        for _ in 0..samples {
+            let sample = tps.read_touch(delay, timeout_us)?;
+            if sample.pressure >= self.config.pressure_threshold {
+                xs[valid] = sample.x;
+                ys[valid] = sample.y;
+                valid += 1;
+            }
+        }
+
+        let point = if valid * 2 > samples {
+            let raw = Point {
+                x: self.config.filter.apply(&mut xs[..valid]) as i32,
+                y: self.config.filter.apply(&mut ys[..valid]) as i32,
+            };
+            Some(self.calibration.apply(raw))
+        } else {
+            None
+        };
+        Ok(self.update(point))
+    }
+
+    /// Advances pen state with a calibrated point, `None` if the screen is not touched
+    pub fn update(&mut self, point: Option<Point>) -> Option<PenEvent> {
+        let debounce = self.config.debounce.max(1);
+        if self.pen_down == point.is_some() {
+            self.pending = 0;
+            return match point {
+                Some(point) if point != self.last => {
+                    self.last = point;
+                    Some(PenEvent::Move(point))
+                }
+                _ => None,
+            };
+        }
+
+        self.pending += 1;
+        if self.pending < debounce {
+            return None;
+        }
+        self.pending = 0;
+        self.pen_down = point.is_some();
+        match point {
+            Some(point) => {
+                self.last = point;
+                Some(PenEvent::Down(point))
+            }
+            None => Some(PenEvent::Up),
+        }
+    }
+}
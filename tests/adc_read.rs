@@ -1,8 +1,14 @@
+use core::convert::TryFrom;
+use core::num::NonZeroU32;
+use std::io::ErrorKind;
+
 use embedded_hal::adc::OneShot;
+use embedded_hal_mock::delay::MockNoop;
 use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+use embedded_hal_mock::MockError;
 
-use tps6507x::channel;
-use tps6507x::Tps6507x;
+use tps6507x::channel::{self, Scale};
+use tps6507x::{AdcInputSelect, Tps6507x, Tps6507xError};
 
 #[test]
 fn test() {
@@ -10,10 +16,11 @@ fn test() {
         // set_adc_vref(true)
         I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0010_0000]),
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b0011_0000]),
-        // poll till conversion finished
-        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0011_0000]),
         // enable adc, set channel and start measure
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1101_1001]),
+        // poll till conversion finished
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1101_1001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1011_1001]),
         // read conversion results
         I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0xA5]),
         I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x02]),
@@ -22,10 +29,175 @@ fn test() {
 
     let mut tps = Tps6507x::new(i2c);
     tps.set_adc_vref(true).unwrap();
-    let sample = tps.read(&mut channel::VoltageBatPins_VrefOn).unwrap();
+    let mut ch = channel::VoltageBatPins_VrefOn;
+    assert!(matches!(tps.read(&mut ch), Err(nb::Error::WouldBlock)));
+    assert!(matches!(tps.read(&mut ch), Err(nb::Error::WouldBlock)));
+    let sample = tps.read(&mut ch).unwrap();
 
     assert_eq!(sample, (0x02 << 8) | 0xA5);
 
     let mut i2c = tps.destroy();
     i2c.done();
 }
+
+#[test]
+fn test_busy() {
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x10]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x01]),
+        // next conversion may use another channel
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_0101]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    assert!(matches!(
+        tps.read(&mut channel::VoltageTsPin),
+        Err(nb::Error::WouldBlock)
+    ));
+    assert!(matches!(
+        tps.read(&mut channel::VoltageIsetPin),
+        Err(nb::Error::Other(Tps6507xError::AdcBusy))
+    ));
+    assert_eq!(tps.read(&mut channel::VoltageTsPin).unwrap(), 0x110);
+    assert!(matches!(
+        tps.read(&mut channel::VoltageIsetPin),
+        Err(nb::Error::WouldBlock)
+    ));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_blocking() {
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x55]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x03]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let sample = tps
+        .read_adc_blocking(&mut channel::InputVoltageCharger, &mut MockNoop, 1000)
+        .unwrap();
+    assert_eq!(sample, 0x355);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_blocking_bus_error() {
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1000]),
+        // bus error while polling
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_1000])
+            .with_error(MockError::Io(ErrorKind::Other)),
+        // failed conversion does not block following ones
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x55]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x03]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let res = tps.read_adc_blocking(&mut channel::InputVoltageCharger, &mut MockNoop, 1000);
+    assert!(matches!(res, Err(Tps6507xError::Interface(_))));
+    let sample = tps
+        .read_adc_blocking(&mut channel::InputVoltageCharger, &mut MockNoop, 1000)
+        .unwrap();
+    assert_eq!(sample, 0x355);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_blocking_timeout() {
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1001]),
+        // ADC never finishes
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0000_1001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0000_1001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0000_1001]),
+        // timed out conversion does not block following ones
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1001]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let res = tps.read_adc_blocking(
+        &mut channel::VoltageBatPins,
+        &mut MockNoop,
+        2 * tps6507x::ADC_POLL_INTERVAL_US as u32,
+    );
+    assert!(matches!(res, Err(Tps6507xError::Timeout)));
+    assert!(matches!(
+        tps.read(&mut channel::VoltageBatPins),
+        Err(nb::Error::WouldBlock)
+    ));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_runtime_input() {
+    assert_eq!(
+        AdcInputSelect::try_from(0b0111),
+        Ok(AdcInputSelect::VoltageSysPin)
+    );
+    assert_eq!(AdcInputSelect::try_from(0b1101), Err(0b1101));
+    assert_eq!(AdcInputSelect::try_from(0x10), Err(0x10));
+    assert_eq!(u8::from(AdcInputSelect::VoltageAcPin), 0b0110);
+
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1101_0111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1011_0111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x00]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x02]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_0110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_0110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0xAA]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x03]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let input = AdcInputSelect::try_from(0b0111).unwrap();
+    let sample = nb::block!(tps.read_adc(input, true)).unwrap();
+    assert_eq!(input.millivolts(sample), 3000);
+
+    let sample = tps
+        .read_adc_input_blocking(AdcInputSelect::VoltageAcPin, false, &mut MockNoop, 1000)
+        .unwrap();
+    assert_eq!(sample, 0x3AA);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_scale() {
+    assert_eq!(channel::VoltageBatPins::millivolts(0), 0);
+    assert_eq!(channel::VoltageBatPins::millivolts(0x2A5), 3967);
+    assert_eq!(channel::VoltageBatPins_VrefOn::millivolts(0x2A5), 3967);
+    assert_eq!(channel::InputVoltageCharger::millivolts(853), 4998);
+    assert_eq!(channel::VoltageAdIn1::millivolts(1023), 2248);
+    assert_eq!(channel::VoltageTsPin::FULL_SCALE_MV, 2250);
+    assert_eq!(channel::VoltageAdIn7::FULL_SCALE_MV, 6000);
+
+    // Icharge = U_ISET / RSET × KISET, 1239 mV × 950 / 1000 Ω and 1239 mV × 950 / 633 Ω
+    assert_eq!(channel::VoltageIsetPin::millivolts(564), 1239);
+    let riset = NonZeroU32::new(1000).unwrap();
+    assert_eq!(channel::charge_current_ma(564, riset), 1177);
+    let riset = NonZeroU32::new(633).unwrap();
+    assert_eq!(channel::charge_current_ma(564, riset), 1859);
+}
@@ -0,0 +1,104 @@
+use embedded_hal_mock::delay::MockNoop;
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{AdcInputSelect, AdcScan, ScanChannel, ScanResult, Tps6507x};
+
+/// Transactions of a conversion finishing on the first poll
+fn conversion(adconfig: u8, sample: u16) -> Vec<I2cTransaction> {
+    vec![
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, adconfig]),
+        I2cTransaction::write_read(
+            tps6507x::SLAVE_ADDR,
+            vec![0x07],
+            vec![adconfig ^ 0b0110_0000],
+        ),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![sample as u8]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![(sample >> 8) as u8]),
+    ]
+}
+
+#[test]
+fn test_run() {
+    let mut expectations = Vec::new();
+    for sample in [0x2A0, 0x2A4, 0x2A3] {
+        expectations.extend(conversion(0b1100_1001, sample));
+    }
+    for sample in [0x100, 0x0F0, 0x110] {
+        expectations.extend(conversion(0b1101_0101, sample));
+    }
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let mut scan = AdcScan::new(
+        [
+            AdcInputSelect::VoltageBatPins.into(),
+            ScanChannel::new(AdcInputSelect::VoltageIsetPin, true),
+        ],
+        3,
+    );
+    let results = scan.run(&mut tps, &mut MockNoop, 1000).unwrap();
+    assert_eq!(
+        results,
+        &[
+            ScanResult {
+                average: 0x2A2,
+                min: 0x2A0,
+                max: 0x2A4,
+            },
+            ScanResult {
+                average: 0x100,
+                min: 0x0F0,
+                max: 0x110,
+            },
+        ]
+    );
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_poll() {
+    let expectations = [
+        // TS pin, second sample takes an extra poll
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x10]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x02]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x14]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x02]),
+        // charger input voltage
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x55]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x03]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x55]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x03]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let mut scan = AdcScan::new(
+        [
+            AdcInputSelect::VoltageTsPin.into(),
+            AdcInputSelect::InputVoltageCharger.into(),
+        ],
+        2,
+    );
+    let mut polls = 1;
+    while let Err(nb::Error::WouldBlock) = scan.poll(&mut tps) {
+        polls += 1;
+    }
+    assert_eq!(polls, 6);
+    assert_eq!(scan.results()[0].average, 0x212);
+    assert_eq!(scan.results()[1].min, 0x355);
+    assert_eq!(scan.results()[1].max, 0x355);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
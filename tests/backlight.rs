@@ -0,0 +1,88 @@
+use embedded_hal::PwmPin;
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{BacklightFade, CurrentLevel, DimmingFrequency, Easing, Tps6507x};
+
+#[test]
+fn test() {
+    let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x18], vec![0b0001_0000]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x18, 0b0011_0000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b0001_1110]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1001_1110]),
+        // 0 % turns the sinks off
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1001_1110]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1111_1111]),
+        // backlight handle reads current brightness
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1111_1111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x18], vec![0b0011_0000]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x18, 0b1011_0000]),
+        // 100 %
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1111_1111]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1110_0011]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_backlight_frequency(DimmingFrequency::V1000Hz)
+        .unwrap();
+    tps.set_backlight_current(CurrentLevel::Iset1).unwrap();
+    tps.set_backlight_brightness(0).unwrap();
+
+    let mut backlight = tps.backlight().unwrap();
+    assert_eq!(backlight.get_duty(), 0);
+    backlight.enable();
+    backlight.set_duty(backlight.get_max_duty());
+    assert_eq!(backlight.get_duty(), 100);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_fade() {
+    let expectations = [
+        // 0 ms: 0 %
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1001_1110]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1111_1111]),
+        // 10 ms: 10 % perceived, 1 % duty
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1111_1111]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1000_0000]),
+        // 14 ms: same duty step, no write
+        // 50 ms: 50 % perceived, 22 % duty
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1000_0000]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1001_0101]),
+        // 100 ms: 100 %
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x19], vec![0b1001_0101]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x19, 0b1110_0011]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    // timer wraps around during the fade
+    let start = u32::MAX - 10;
+    let mut fade = BacklightFade::new(0, 100, 100, Easing::Linear, start);
+
+    assert!(!fade.tick(&mut tps, start).unwrap());
+    assert!(!fade.tick(&mut tps, start.wrapping_add(10)).unwrap());
+    assert!(!fade.tick(&mut tps, start.wrapping_add(14)).unwrap());
+    assert!(!fade.tick(&mut tps, start.wrapping_add(50)).unwrap());
+    assert!(fade.tick(&mut tps, start.wrapping_add(100)).unwrap());
+    assert!(fade.tick(&mut tps, start.wrapping_add(150)).unwrap());
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_easing() {
+    let fade = BacklightFade::new(100, 0, 1000, Easing::EaseIn, 0);
+    assert_eq!(fade.level(0), 100);
+    assert_eq!(fade.level(500), 75);
+    assert_eq!(fade.level(1000), 0);
+
+    let fade = BacklightFade::new(0, 100, 1000, Easing::EaseInOut, 0);
+    assert_eq!(fade.level(250), 12);
+    assert_eq!(fade.level(500), 50);
+    assert_eq!(fade.level(750), 87);
+}
@@ -7,8 +7,11 @@ use tps6507x::Tps6507x;
 #[test]
 fn test() {
     let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0x3F]),
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x10, 0x2D]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x33]),
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x12, 0x3F]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x14], vec![0x13]),
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x14, 0x1F]),
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x17, 0x3F]),
         I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x04, 0x30]),
@@ -30,8 +33,8 @@ fn test() {
     })
     .unwrap();
 
-    assert_eq!(tps.usb_power().unwrap(), true);
-    assert_eq!(tps.usb_power().unwrap(), false);
+    assert!(tps.usb_power().unwrap());
+    assert!(!tps.usb_power().unwrap());
 
     let mut i2c = tps.destroy();
     i2c.done();
@@ -0,0 +1,32 @@
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{ChargeState, Tps6507x};
+
+#[test]
+fn test_status() {
+    let expectations = [
+        // USB present, charging with DPPM loop active
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x01], vec![0b1000_1101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x03], vec![0b0100_0000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x06], vec![0b0110_0110]),
+        // AC present, precharge timer timed out
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x01], vec![0b0100_1101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x03], vec![0b0000_0010]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x06], vec![0b0110_0100]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+
+    let status = tps.charger_status().unwrap();
+    assert!(status.usb_power && !status.ac_power);
+    assert!(status.dppm_active);
+    assert_eq!(status.state(), ChargeState::Charging);
+
+    let status = tps.charger_status().unwrap();
+    assert!(status.precharge_timeout);
+    assert_eq!(status.state(), ChargeState::Fault);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
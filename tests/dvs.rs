@@ -0,0 +1,73 @@
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{DCDCVoltage, Dcdc, Tps6507x, VoltageAdjust};
+
+#[test]
+fn test_setpoints() {
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x11, 0x0B]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x3F]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x12, 0x13]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x13, 0x07]),
+        // DEFDCDC2 pin is HIGH
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x13]),
+        // DEFDCDC3 pin is LOW
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x13], vec![0x07]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_dcdc2_low(DCDCVoltage::V1_000).unwrap();
+    tps.set_dcdc2_high(DCDCVoltage::V1_200).unwrap();
+    tps.set_dcdc3_low(DCDCVoltage::V0_900).unwrap();
+
+    assert!(matches!(
+        tps.dcdc2_active(true).unwrap(),
+        DCDCVoltage::V1_200
+    ));
+    assert!(matches!(
+        tps.dcdc3_active(false).unwrap(),
+        DCDCVoltage::V0_900
+    ));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_external_adjust() {
+    let expectations = [
+        // switch DCDC1 to external resistor divider
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0x3F]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x10, 0xBF]),
+        // voltage change keeps extadj bit
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0xBF]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x10, 0xA5]),
+        // DCDC2 mode is stored in DEFDCDC2_HIGH
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x12]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x12, 0x92]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0xA5]),
+        // DCDC3 mode is stored in DEFDCDC3_HIGH
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x14], vec![0x19]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_dcdc_adjust(Dcdc::Dcdc1, VoltageAdjust::External)
+        .unwrap();
+    tps.set_dcdc1(DCDCVoltage::V1_800).unwrap();
+    tps.set_dcdc_adjust(Dcdc::Dcdc2, VoltageAdjust::External)
+        .unwrap();
+
+    assert_eq!(
+        tps.dcdc_adjust(Dcdc::Dcdc1).unwrap(),
+        VoltageAdjust::External
+    );
+    assert_eq!(
+        tps.dcdc_adjust(Dcdc::Dcdc3).unwrap(),
+        VoltageAdjust::Internal
+    );
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
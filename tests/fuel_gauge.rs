@@ -0,0 +1,84 @@
+use embedded_hal_mock::delay::MockNoop;
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{BatteryLevel, ChargeState, FuelGauge, FuelGaugeConfig, Tps6507x};
+
+#[test]
+fn test_lookup() {
+    let mut gauge = FuelGauge::default();
+    let soc = gauge.update(3820, ChargeState::Idle);
+    assert_eq!(soc.percent, 50);
+    assert_eq!(soc.level, BatteryLevel::Normal);
+
+    gauge.reset();
+    assert_eq!(gauge.update(3795, ChargeState::Done).percent, 42);
+    gauge.reset();
+    assert_eq!(gauge.update(4300, ChargeState::Idle).percent, 100);
+    gauge.reset();
+    assert_eq!(gauge.update(2800, ChargeState::Idle).percent, 0);
+}
+
+#[test]
+fn test_compensation() {
+    let mut gauge = FuelGauge::default();
+    let soc = gauge.update(3920, ChargeState::Charging);
+    assert_eq!((soc.ocv_mv, soc.percent), (3820, 50));
+
+    gauge.reset();
+    let soc = gauge.update(3770, ChargeState::NoSource);
+    assert_eq!((soc.ocv_mv, soc.percent), (3820, 50));
+}
+
+#[test]
+fn test_filter() {
+    let mut gauge = FuelGauge::default();
+    assert_eq!(gauge.update(3820, ChargeState::Idle).percent, 50);
+    let soc = gauge.update(3980, ChargeState::Idle);
+    assert_eq!((soc.ocv_mv, soc.percent), (3840, 54));
+
+    // load released, percentage does not rise while discharging
+    gauge.reset();
+    assert_eq!(gauge.update(3770, ChargeState::NoSource).percent, 50);
+    assert_eq!(gauge.update(3900, ChargeState::NoSource).percent, 50);
+}
+
+#[test]
+fn test_level() {
+    let config = FuelGaugeConfig::default();
+    let mut gauge = FuelGauge::new(config);
+    assert_eq!(
+        gauge.update(3700, ChargeState::Idle).level,
+        BatteryLevel::Low
+    );
+    gauge.reset();
+    assert_eq!(
+        gauge.update(3450, ChargeState::Idle).level,
+        BatteryLevel::Critical
+    );
+}
+
+#[test]
+fn test_measure() {
+    let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0000_0000]),
+        // battery voltage conversion
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x83]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x02]),
+        // running from battery
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x01], vec![0b0000_1101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x03], vec![0b0000_0000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x06], vec![0b0110_0000]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let mut gauge = FuelGauge::default();
+    let soc = gauge.measure(&mut tps, &mut MockNoop, 1000).unwrap();
+    assert_eq!(soc.ocv_mv, 3818);
+    assert_eq!(soc.percent, 49);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
@@ -0,0 +1,40 @@
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{InterruptEvents, InterruptMask, Tps6507x};
+
+#[test]
+fn test() {
+    let expectations = [
+        // unmask push button and touch screen
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x02, 0b0110_0000]),
+        // push button pressed and power source applied
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x02], vec![0b0110_0110]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_interrupt_mask(InterruptMask {
+        touch_screen: true,
+        push_button: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let events = tps.service_interrupt().unwrap();
+    assert_eq!(
+        events,
+        InterruptEvents {
+            power_applied: true,
+            push_button: true,
+            ..Default::default()
+        }
+    );
+
+    // mask read does not touch INT, so no events are cleared
+    let mask = tps.interrupt_mask();
+    assert!(!mask.power_source);
+    assert!(mask.touch_screen && mask.push_button);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
@@ -0,0 +1,54 @@
+use embedded_hal_mock::delay::MockNoop;
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{PowerGoodMask, Rail, RailSet, Tps6507x, Tps6507xError};
+
+#[test]
+fn test_wait() {
+    let expectations = [
+        // PGOOD driven by DCDC1, DCDC2 and DCDC3
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0C, 0b0001_1100]),
+        // DCDC3 is still ramping
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0B], vec![0b1111_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0B], vec![0b0111_1100]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let rails = RailSet {
+        dcdc1: true,
+        dcdc2: true,
+        dcdc3: true,
+        ..Default::default()
+    };
+    tps.set_power_good_mask(PowerGoodMask {
+        rails,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let status = tps.wait_power_good(rails, &mut MockNoop, 10).unwrap();
+    assert!(status.rails.contains(Rail::Dcdc3));
+    assert!(!status.rails.contains(Rail::Ldo1));
+    assert!(!status.threshold_low);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_timeout() {
+    let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0B], vec![0b0111_1101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0B], vec![0b0111_1101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0B], vec![0b0111_1101]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let result = tps.wait_power_good(Rail::Ldo1.into(), &mut MockNoop, 2);
+    assert!(matches!(result, Err(Tps6507xError::Timeout)));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
@@ -0,0 +1,95 @@
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{Dcdc, DcdcSequence, LdoSequence, Rail, RailSet, SequenceMode, Tps6507x};
+
+#[test]
+fn test() {
+    let expectations = [
+        // disable LDO2, sequencing bits are kept
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1111]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0D, 0b0111_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1110]),
+        // bulk update
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1110]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0D, 0b0111_0011]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_0011]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.disable_rail(Rail::Ldo2).unwrap();
+    assert!(!tps.rail_enabled(Rail::Ldo2).unwrap());
+
+    tps.set_rails(RailSet {
+        dcdc1: true,
+        ldo1: true,
+        ldo2: true,
+        ..Default::default()
+    })
+    .unwrap();
+    let rails = tps.rails().unwrap();
+    assert!(rails.contains(Rail::Dcdc1));
+    assert!(!rails.contains(Rail::Dcdc2) && !rails.contains(Rail::Dcdc3));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_sequence() {
+    let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1111]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0D, 0b1101_1111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x16], vec![0b0010_1001]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x16, 0b0110_1001]),
+        // TPS65070 defaults
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x16], vec![0b0010_1001]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_sequence(SequenceMode {
+        dcdc: DcdcSequence::Dcdc1ThenDcdc2ThenDcdc3,
+        ldo: LdoSequence::Ldo1AfterDcdc1,
+    })
+    .unwrap();
+
+    assert_eq!(
+        tps.sequence().unwrap(),
+        SequenceMode {
+            dcdc: DcdcSequence::Dcdc3ThenDcdc2,
+            ldo: LdoSequence::AfterDcdc3,
+        }
+    );
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_converter_modes() {
+    let expectations = [
+        // force PWM on DCDC2
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0F], vec![0b0001_1111]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0F, 0b0101_1111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0F], vec![0b0101_1111]),
+        // disable LDO1 discharge
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0F], vec![0b0101_1111]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0F, 0b0101_1101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0F], vec![0b0101_1101]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_forced_pwm(Dcdc::Dcdc2, true).unwrap();
+    assert!(tps.forced_pwm(Dcdc::Dcdc2).unwrap());
+
+    tps.set_discharge(Rail::Ldo1, false).unwrap();
+    let discharge = tps.discharge().unwrap();
+    assert!(!discharge.contains(Rail::Ldo1));
+    assert!(discharge.contains(Rail::Dcdc1) && discharge.contains(Rail::Ldo2));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
@@ -0,0 +1,58 @@
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{
+    AcInputCurrent, ChargerConfig, DCDCVoltage, LDO1Voltage, PowerPath, SafetyTimerTimeOut,
+    SensorType, Tps6507x, UsbInputCurrent,
+};
+
+#[test]
+fn test() {
+    let expectations = [
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x01, 0b0010_1001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x01], vec![0b1010_1001]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x04, 0b1001_0001]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x04], vec![0b1001_0001]),
+        // extadj bit is not a part of the voltage
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0x80 | 0x2D]),
+        // ldo sequencing bits are not a part of the voltage
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x16], vec![0b0010_1001]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+
+    tps.set_power_path(PowerPath {
+        usb_power_enable: false,
+        usb_input_current: UsbInputCurrent::V500mA,
+        ac_power_enable: true,
+        ac_input_current: AcInputCurrent::V1300mA,
+    })
+    .unwrap();
+    let power_path = tps.power_path().unwrap();
+    assert!(!power_path.usb_power_enable);
+    assert!(power_path.ac_power_enable);
+    assert_eq!(power_path.usb_input_current, UsbInputCurrent::V500mA);
+    assert_eq!(power_path.ac_input_current, AcInputCurrent::V1300mA);
+
+    tps.set_charger_config(ChargerConfig {
+        safety_timer_enable: false,
+        charger_safety_timer_timeout: SafetyTimerTimeOut::V6Hours,
+        ..Default::default()
+    })
+    .unwrap();
+    let config = tps.charger_config().unwrap();
+    assert!(config.charger_enable);
+    assert!(config.charge_termination);
+    assert!(!config.safety_timer_enable);
+    assert_eq!(config.sensor_type, SensorType::V10K);
+    assert_eq!(
+        config.charger_safety_timer_timeout,
+        SafetyTimerTimeOut::V6Hours
+    );
+
+    assert!(matches!(tps.dcdc1().unwrap(), DCDCVoltage::V2_200));
+    assert!(matches!(tps.ldo1().unwrap(), LDO1Voltage::V1_8));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
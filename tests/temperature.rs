@@ -0,0 +1,105 @@
+use embedded_hal_mock::delay::MockNoop;
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{BiasNetwork, NtcModel, NtcPoint, SensorType, Thermistor, Tps6507x, V_NO_NTC_MV};
+
+fn assert_celsius(actual: Option<f32>, expected: f32) {
+    let actual = actual.unwrap();
+    assert!(
+        (actual - expected).abs() < 1.5,
+        "{} °C, expected {} °C",
+        actual,
+        expected
+    );
+}
+
+#[test]
+fn test_trip_points() {
+    // VCOLD and VHOT charger trip points
+    for sensor in [SensorType::V10K, SensorType::V100K] {
+        let thermistor = Thermistor::for_sensor(sensor);
+        assert_celsius(thermistor.celsius(1660), 0.0);
+        assert_celsius(thermistor.celsius(860), 45.0);
+        // no NTC connected, open TS pin is about 2.05 V
+        assert_eq!(thermistor.celsius(2200), None);
+        assert_eq!(thermistor.celsius(2050), None);
+        assert_eq!(thermistor.celsius(V_NO_NTC_MV), None);
+        assert_eq!(thermistor.celsius(0), None);
+    }
+}
+
+#[test]
+fn test_sensor_models() {
+    // datasheet Table 15 B values, NTC resistance at 0 °C
+    let points = [(SensorType::V10K, 29_077.0), (SensorType::V100K, 337_654.0)];
+    for (sensor, ohms) in points {
+        let celsius = NtcModel::for_sensor(sensor).celsius(ohms);
+        assert!(celsius.abs() < 0.05, "{} °C, expected 0 °C", celsius);
+    }
+}
+
+#[test]
+fn test_models() {
+    let ohms = 10_000.0;
+    let beta = NtcModel::Beta {
+        r25_ohms: 10_000.0,
+        beta: 3380.0,
+    };
+    assert_celsius(Some(beta.celsius(ohms)), 25.0);
+
+    // 10 kOhm NTC, B = 3435
+    let steinhart_hart = NtcModel::SteinhartHart {
+        a: 1.1292e-3,
+        b: 2.3411e-4,
+        c: 8.7755e-8,
+    };
+    assert_celsius(Some(steinhart_hart.celsius(ohms)), 25.0);
+
+    static TABLE: [NtcPoint; 3] = [
+        NtcPoint {
+            celsius: 0.0,
+            ohms: 27_000,
+        },
+        NtcPoint {
+            celsius: 25.0,
+            ohms: 10_000,
+        },
+        NtcPoint {
+            celsius: 50.0,
+            ohms: 4_000,
+        },
+    ];
+    let table = NtcModel::Table(&TABLE);
+    assert_eq!(table.celsius(10_000.0), 25.0);
+    assert_eq!(table.celsius(7_000.0), 37.5);
+    assert_eq!(table.celsius(40_000.0), 0.0);
+    assert_eq!(table.celsius(1_000.0), 50.0);
+
+    let bias = BiasNetwork {
+        bias_mv: 2000,
+        pullup_ohms: 10_000,
+        parallel_ohms: None,
+    };
+    assert_eq!(bias.ntc_ohms(1000), Some(10_000.0));
+}
+
+#[test]
+fn test_read() {
+    let expectations = [
+        // 10k NTC selected
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x04], vec![0b0001_0000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0000_0000]),
+        // TS pin conversion
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0xF3]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x02]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    assert_celsius(tps.battery_temperature(&mut MockNoop, 1000).unwrap(), 0.0);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
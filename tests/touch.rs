@@ -0,0 +1,250 @@
+use embedded_hal_mock::delay::MockNoop;
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{
+    AdcInputSelect, Calibration, Filter, InterruptMask, PenEvent, Point, TouchConfig, TouchSample,
+    TouchScreen, TouchScreenMode, Tps6507x, Tps6507xError,
+};
+
+/// Transactions of a `read_touch` call with the reference LDO off
+fn touch_sample(x: u16, y: u16, pressure: u16) -> Vec<I2cTransaction> {
+    let mut transactions = vec![I2cTransaction::write_read(
+        tps6507x::SLAVE_ADDR,
+        vec![0x07],
+        vec![0b1000_1110],
+    )];
+    for (mode, value) in [(0b000, x), (0b001, y), (0b010, pressure)] {
+        transactions.extend([
+            I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, mode]),
+            I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1110]),
+            I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1110]),
+            I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![value as u8]),
+            I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![(value >> 8) as u8]),
+        ]);
+    }
+    transactions.push(I2cTransaction::write(
+        tps6507x::SLAVE_ADDR,
+        vec![0x08, 0b0000_0101],
+    ));
+    transactions
+}
+
+#[test]
+fn test_pen_down() {
+    let expectations = [
+        // standby, ADC on with AD_IN14 without starting a conversion, clear TSC INT raised by
+        // configuration
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, 0b0000_0101]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b0101_0000]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1001_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x02], vec![0b0000_1010]),
+        // unmask touch interrupt
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x02, 0b0100_0000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x02], vec![0b0100_0100]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x02], vec![0b0100_1000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x08], vec![0b0000_0101]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    // events latched before are not lost
+    let events = tps.enable_touch_detect().unwrap();
+    assert!(events.power_applied && !events.touch_screen);
+    tps.set_interrupt_mask(InterruptMask {
+        touch_screen: true,
+        ..Default::default()
+    })
+    .unwrap();
+    let (touch, events) = tps.pen_down().unwrap();
+    assert!(!touch && events.push_button);
+    let (touch, events) = tps.pen_down().unwrap();
+    assert!(touch && events.is_empty());
+    assert_eq!(tps.touch_screen_mode().unwrap(), TouchScreenMode::Standby);
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_touch_detect_busy() {
+    let expectations = [
+        // pending conversion of the battery voltage
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1001]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    assert!(matches!(
+        tps.read_adc(AdcInputSelect::VoltageBatPins, false),
+        Err(nb::Error::WouldBlock)
+    ));
+    assert!(matches!(
+        tps.enable_touch_detect(),
+        Err(Tps6507xError::AdcBusy)
+    ));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_read_touch() {
+    let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1000_1110]),
+        // X-position, one poll before end of conversion
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, 0b0000_0000]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x34]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x01]),
+        // Y-position
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, 0b0000_0001]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0xFF]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0xFE]),
+        // pressure
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, 0b0000_0010]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1010_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x09], vec![0x80]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0A], vec![0x00]),
+        // back to standby
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, 0b0000_0101]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let sample = tps.read_touch(&mut MockNoop, 1000).unwrap();
+    assert_eq!(
+        sample,
+        TouchSample {
+            x: 0x134,
+            y: 0x2FF,
+            pressure: 0x80,
+        }
+    );
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_timeout() {
+    let expectations = [
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1000_1110]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x08, 0b0000_0000]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x07, 0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_1110]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x07], vec![0b1100_1110]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let res = tps.read_touch(&mut MockNoop, 2 * tps6507x::ADC_POLL_INTERVAL_US as u32);
+    assert!(matches!(res, Err(Tps6507xError::Timeout)));
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_calibration() {
+    // panel mounted upside down, 800x480 screen
+    let raw = [
+        Point::new(900, 850),
+        Point::new(120, 500),
+        Point::new(520, 150),
+    ];
+    let screen = [
+        Point::new(80, 48),
+        Point::new(720, 240),
+        Point::new(400, 432),
+    ];
+    let calibration = Calibration::from_points(raw, screen).unwrap();
+    for (raw, screen) in raw.iter().zip(screen.iter()) {
+        assert_eq!(calibration.apply(*raw), *screen);
+    }
+
+    let restored = Calibration::from_bytes(&calibration.to_bytes()).unwrap();
+    assert_eq!(restored, calibration);
+    assert!(Calibration::from_bytes(&[0xFF; Calibration::BYTES]).is_none());
+    assert!(Calibration::from_bytes(&[0x00; Calibration::BYTES]).is_none());
+    let mut corrupted = calibration.to_bytes();
+    corrupted[3] ^= 0x10;
+    assert!(Calibration::from_bytes(&corrupted).is_none());
+    assert!(Calibration::from_points(
+        [Point::new(0, 0), Point::new(10, 10), Point::new(20, 20)],
+        screen
+    )
+    .is_none());
+    assert_eq!(
+        Calibration::default().apply(Point::new(12, 34)),
+        Point::new(12, 34)
+    );
+}
+
+#[test]
+fn test_filter() {
+    assert_eq!(Filter::Median.apply(&mut [500, 1020, 498, 502, 3]), 500);
+    assert_eq!(Filter::Average.apply(&mut [500, 502, 498, 504]), 501);
+    assert_eq!(Filter::Median.apply(&mut []), 0);
+}
+
+#[test]
+fn test_debounce() {
+    let mut touch = TouchScreen::new(TouchConfig::default(), Calibration::default());
+    let p = Point::new(100, 200);
+
+    assert_eq!(touch.update(Some(p)), None);
+    assert_eq!(touch.update(None), None);
+    assert_eq!(touch.update(Some(p)), None);
+    assert_eq!(touch.update(Some(p)), Some(PenEvent::Down(p)));
+    assert!(touch.is_pen_down());
+    assert_eq!(touch.update(Some(p)), None);
+    assert_eq!(
+        touch.update(Some(Point::new(101, 200))),
+        Some(PenEvent::Move(Point::new(101, 200)))
+    );
+    assert_eq!(touch.update(None), None);
+    assert_eq!(touch.update(None), Some(PenEvent::Up));
+    assert!(!touch.is_pen_down());
+}
+
+#[test]
+fn test_poll() {
+    let mut expectations = Vec::new();
+    // last sample lost contact, X and Y of remaining two are averaged
+    expectations.extend(touch_sample(400, 600, 200));
+    expectations.extend(touch_sample(410, 620, 180));
+    expectations.extend(touch_sample(1023, 0, 2));
+    // no contact
+    expectations.extend(touch_sample(400, 600, 200));
+    expectations.extend(touch_sample(0, 1023, 0));
+    expectations.extend(touch_sample(0, 1023, 1));
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    let mut touch = TouchScreen::new(
+        TouchConfig {
+            samples: 3,
+            filter: Filter::Average,
+            pressure_threshold: 32,
+            debounce: 1,
+        },
+        Calibration::default(),
+    );
+    assert_eq!(
+        touch.poll(&mut tps, &mut MockNoop, 1000).unwrap(),
+        Some(PenEvent::Down(Point::new(405, 610)))
+    );
+    assert_eq!(
+        touch.poll(&mut tps, &mut MockNoop, 1000).unwrap(),
+        Some(PenEvent::Up)
+    );
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
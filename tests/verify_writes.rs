@@ -0,0 +1,73 @@
+use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTransaction};
+
+use tps6507x::{DCDCVoltage, InterruptMask, Rail, Registers, Tps6507x, Tps6507xError};
+
+#[test]
+fn test() {
+    let expectations = [
+        // verified write
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0x3F]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x10, 0x2D]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x10], vec![0x2D]),
+        // INT is not read back, latched events are kept for service_interrupt
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x02, 0b1000_0000]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x02], vec![0b1000_0011]),
+        // voltage did not take effect
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x33]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x12, 0x3F]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x33]),
+        // verification disabled
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x12], vec![0x33]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x12, 0x3F]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_verify_writes(true);
+
+    tps.set_dcdc1(DCDCVoltage::V2_200).unwrap();
+    tps.set_interrupt_mask(InterruptMask {
+        power_source: true,
+        ..Default::default()
+    })
+    .unwrap();
+    let events = tps.service_interrupt().unwrap();
+    assert!(events.power_applied && events.power_removed);
+
+    match tps.set_dcdc2_high(DCDCVoltage::V3_300) {
+        Err(Tps6507xError::ReadBackMismatch {
+            register,
+            expected,
+            actual,
+        }) => {
+            assert_eq!(register, Registers::DEFDCDC2_HIGH);
+            assert_eq!(expected, 0x3F);
+            assert_eq!(actual, 0x33);
+        }
+        _ => panic!("read back mismatch expected"),
+    }
+
+    tps.set_verify_writes(false);
+    tps.set_dcdc2_high(DCDCVoltage::V3_300).unwrap();
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}
+
+#[test]
+fn test_rail_enable_pin_low() {
+    let expectations = [
+        // enable bit of DCDC3 is reset while its enable pin is LOW
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1011]),
+        I2cTransaction::write(tps6507x::SLAVE_ADDR, vec![0x0D, 0b0111_1111]),
+        I2cTransaction::write_read(tps6507x::SLAVE_ADDR, vec![0x0D], vec![0b0111_1011]),
+    ];
+    let i2c = I2cMock::new(&expectations);
+
+    let mut tps = Tps6507x::new(i2c);
+    tps.set_verify_writes(true);
+    tps.enable_rail(Rail::Dcdc3).unwrap();
+
+    let mut i2c = tps.destroy();
+    i2c.done();
+}